@@ -0,0 +1,274 @@
+//! The errors of derived conversions
+
+use std::error::Error;
+use std::fmt;
+
+use crate::__private::{self as private, ElementFailure, Invalid};
+use crate::{FieldPath, PathSegment};
+
+/// The error of a derived conversion, which tells the paths of all fields that failed,
+/// including those within nested conversions.
+///
+/// It is implemented by [`MissingFields`] and [`ElementErrors`], as well as by the generated error types.
+pub trait ConversionError: Error + Send + Sync + 'static {
+    /// The paths of all fields that were missing, e.g. `header.id`.
+    fn missing_paths(&self) -> Vec<FieldPath>;
+
+    /// The paths of all fields that could not be converted, along with the respective error.
+    fn invalid_paths(&self) -> Vec<(FieldPath, &(dyn Error + 'static))>;
+}
+
+/// The error of a failed conversion from a super type, shared by all sub types
+/// deriving `FromSuper` with the `shared_error` option.
+///
+/// It tells which fields were `None` in the super type,
+/// and which fields could not be converted (e.g. when using `nested` or `try_with`),
+/// or why the converted value failed validation.
+/// When converting enums, it also tells the variant of the super enum,
+/// and whether this variant is not modeled by the sub enum at all.
+#[derive(Debug)]
+pub struct MissingFields {
+    sub_type: &'static str,
+    super_type: &'static str,
+    variant: Option<&'static str>,
+    unmodeled: bool,
+    missing: Vec<(&'static str, &'static str)>,
+    invalid: Vec<(&'static str, Invalid)>,
+    validation: Option<Invalid>,
+}
+
+impl MissingFields {
+    #[doc(hidden)]
+    pub fn new(sub_type: &'static str, super_type: &'static str) -> Self {
+        MissingFields {
+            sub_type,
+            super_type,
+            variant: None,
+            unmodeled: false,
+            missing: Vec::new(),
+            invalid: Vec::new(),
+            validation: None,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn new_variant(
+        sub_type: &'static str,
+        super_type: &'static str,
+        variant: &'static str,
+    ) -> Self {
+        MissingFields {
+            variant: Some(variant),
+            ..Self::new(sub_type, super_type)
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn unmodeled(
+        sub_type: &'static str,
+        super_type: &'static str,
+        variant: Option<&'static str>,
+    ) -> Self {
+        MissingFields {
+            variant,
+            unmodeled: true,
+            ..Self::new(sub_type, super_type)
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn push(&mut self, field: &'static str, source: &'static str) {
+        self.missing.push((field, source));
+    }
+
+    #[doc(hidden)]
+    pub fn push_invalid(&mut self, field: &'static str, invalid: Invalid) {
+        self.invalid.push((field, invalid));
+    }
+
+    #[doc(hidden)]
+    pub fn fail_validation(&mut self, invalid: Invalid) {
+        self.validation = Some(invalid);
+    }
+
+    #[doc(hidden)]
+    pub fn any_failed(&self) -> bool {
+        !self.missing.is_empty() || !self.invalid.is_empty()
+    }
+
+    /// The full name of the sub type, including its generic arguments.
+    pub fn sub_type_name(&self) -> &'static str {
+        self.sub_type
+    }
+
+    /// The full name of the super type, including its generic arguments.
+    pub fn super_type_name(&self) -> &'static str {
+        self.super_type
+    }
+
+    /// The variant of the super enum that was converted, if known.
+    pub fn variant(&self) -> Option<&'static str> {
+        self.variant
+    }
+
+    /// Whether the variant of the super enum is not modeled by the sub enum.
+    pub fn is_unmodeled(&self) -> bool {
+        self.unmodeled
+    }
+
+    /// The fields of the sub type that were missing in the super type.
+    pub fn missing_fields(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.missing.iter().map(|(field, _)| *field)
+    }
+
+    /// Whether the given field of the sub type was missing in the super type.
+    pub fn is_missing(&self, field: &str) -> bool {
+        self.missing.iter().any(|(missing, _)| *missing == field)
+    }
+
+    /// The names of the missing fields within the super type.
+    pub fn missing_sources(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.missing.iter().map(|(_, source)| *source)
+    }
+
+    /// The fields of the sub type that could not be converted.
+    pub fn invalid_fields(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.invalid.iter().map(|(field, _)| *field)
+    }
+
+    /// The error of the validation of the converted value, if it failed.
+    pub fn validation_error(&self) -> Option<&(dyn Error + 'static)> {
+        self.validation.as_ref().map(Invalid::error)
+    }
+}
+
+impl fmt::Display for MissingFields {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.unmodeled {
+            return match self.variant {
+                Some(variant) => write!(
+                    f,
+                    "Variant {} of the super enum {} is not modeled",
+                    variant, self.super_type
+                ),
+                None => write!(
+                    f,
+                    "Encountered a variant of the super enum {} that is not modeled",
+                    self.super_type
+                ),
+            };
+        }
+
+        if let Some(ref validation) = self.validation {
+            return private::fmt_validation_error(f, self.sub_type, validation);
+        }
+
+        let super_part = match self.variant {
+            Some(variant) => format!("variant {} of the super enum {}", variant, self.super_type),
+            None => format!("the super struct {}", self.super_type),
+        };
+
+        private::fmt_error(f, &super_part, &self.missing_paths(), &self.invalid_paths())
+    }
+}
+
+impl Error for MissingFields {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.validation_error()
+            .or_else(|| self.invalid.first().map(|(_, invalid)| invalid.error()))
+    }
+}
+
+impl ConversionError for MissingFields {
+    fn missing_paths(&self) -> Vec<FieldPath> {
+        private::missing_paths(
+            self.missing_fields(),
+            self.invalid
+                .iter()
+                .map(|(field, invalid)| (*field, invalid)),
+        )
+    }
+
+    fn invalid_paths(&self) -> Vec<(FieldPath, &(dyn Error + 'static))> {
+        private::invalid_paths(
+            self.invalid
+                .iter()
+                .map(|(field, invalid)| (*field, invalid)),
+        )
+    }
+}
+
+/// The error of converting a collection element by element (using the field options
+/// `nested_elements` or `unpack_elements`), which tells the elements that were missing
+/// or could not be converted, given by their index or key.
+#[derive(Debug, Default)]
+pub struct ElementErrors {
+    missing: Vec<PathSegment>,
+    invalid: Vec<(PathSegment, Invalid)>,
+}
+
+impl ElementErrors {
+    #[doc(hidden)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[doc(hidden)]
+    pub fn push(&mut self, element: PathSegment, failure: ElementFailure) {
+        match failure {
+            ElementFailure::Missing => self.missing.push(element),
+            ElementFailure::Invalid(invalid) => self.invalid.push((element, invalid)),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn any_failed(&self) -> bool {
+        !self.missing.is_empty() || !self.invalid.is_empty()
+    }
+
+    /// The elements that could not be unpacked, e.g. because they were `None`.
+    pub fn missing_elements(&self) -> impl Iterator<Item = &PathSegment> + '_ {
+        self.missing.iter()
+    }
+
+    /// The elements that could not be converted.
+    pub fn invalid_elements(&self) -> impl Iterator<Item = &PathSegment> + '_ {
+        self.invalid.iter().map(|(element, _)| element)
+    }
+}
+
+impl fmt::Display for ElementErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        private::fmt_error(
+            f,
+            "the collection",
+            &self.missing_paths(),
+            &self.invalid_paths(),
+        )
+    }
+}
+
+impl Error for ElementErrors {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.invalid.first().map(|(_, invalid)| invalid.error())
+    }
+}
+
+impl ConversionError for ElementErrors {
+    fn missing_paths(&self) -> Vec<FieldPath> {
+        private::missing_segment_paths(
+            self.missing.iter().cloned(),
+            self.invalid
+                .iter()
+                .map(|(element, invalid)| (element.clone(), invalid)),
+        )
+    }
+
+    fn invalid_paths(&self) -> Vec<(FieldPath, &(dyn Error + 'static))> {
+        private::invalid_segment_paths(
+            self.invalid
+                .iter()
+                .map(|(element, invalid)| (element.clone(), invalid)),
+        )
+    }
+}
@@ -132,6 +132,39 @@
 //! # }
 //! ```
 //!
+//! Besides `Option`s, any type implementing the trait `fromsuper::Unpack` can be unpacked,
+//! such as `Box<Option<T>>` or custom wrapper types.
+//! Converting back into the super struct additionally requires `fromsuper::Pack`,
+//! which wraps the unpacked values again.
+//!
+//! Several layers of `Option`s (or other unpackable types) can be unpacked at once using
+//! the field option `unpack_depth`, e.g. `unpack_depth = 2` for an `Option<Option<T>>`.
+//! The field is regarded as missing if any of the layers is missing.
+//!
+//! Fields of the super struct can also be `Result`s, which are unpacked using the field option
+//! `unpack_result`. This does not require the whole struct to be unpacked.
+//! If the source value is an `Err`, the conversion fails and its error is kept in the
+//! conversion error, just like the errors of `try_with` functions:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct Bar {
+//!     a: Option<u32>,
+//!     b: Result<u16, std::num::ParseIntError>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar", unpack = true)]
+//! struct Foo {
+//!     a: u32,
+//!     #[fromsuper(unpack_result)]
+//!     b: u16,
+//! }
+//! ```
+//!
+//! When converting from a borrowed super struct (e.g. using `make_refs`, `clone` or `cow`),
+//! the error is cloned into the conversion error, so its type needs to implement `Clone`.
+//!
 //! ## Generics
 //!
 //! `derive(FromSuper)` can handle many situations in which generics are involved.
@@ -169,8 +202,6 @@
 //! the derived sub struct can be made to contain only references to the
 //! original values instead of consuming them.
 //! This behavior can be activated by using the `make_refs` argument.
-//! Note that this can only be activated for the whole struct,
-//! not on a per-field basis.
 //!
 //! ```rust
 //! # use fromsuper::FromSuper;
@@ -187,6 +218,758 @@
 //!     b: &'a String,
 //! }
 //! ```
+//!
+//! References can also be made for single fields only, using the field option `make_ref`,
+//! or the struct-level `make_refs` can be disabled for single fields using `make_ref = false`.
+//! Fields that are not referenced are copied from the borrowed super struct:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct Bar {
+//!     id: Option<u32>,
+//!     name: String,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "&'a Bar", unpack = true)]
+//! struct Foo<'a> {
+//!     id: u32,
+//!     #[fromsuper(make_ref, unpack = false)]
+//!     name: &'a String,
+//! }
+//! ```
+//!
+//! The referenced fields can also be given in their dereferenced forms,
+//! e.g. `&'a str` for a `String`, `&'a [T]` for a `Vec<T>`, or `&'a T` for a `Box<T>`.
+//! Source fields that are `Option`s and not unpacked can be referenced as `Option<&'a T>`
+//! instead of `&'a Option<T>`, where `T` can be any borrowed form of the source value
+//! (e.g. `Option<&'a str>` for an `Option<String>`):
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct Bar {
+//!     name: Option<String>,
+//!     nickname: Option<String>,
+//!     data: Vec<u8>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "&'a Bar", unpack = true, make_refs = true)]
+//! struct Foo<'a> {
+//!     name: &'a str,
+//!     #[fromsuper(unpack = false)]
+//!     nickname: Option<&'a str>,
+//!     #[fromsuper(unpack = false)]
+//!     data: &'a [u8],
+//! }
+//! ```
+//!
+//! Mutable references can be made using `make_refs = "mut"` (or `make_ref = "mut"` for single
+//! fields), given that the super struct is borrowed mutably. This way, a sub struct can
+//! serve as a view for editing a part of the super struct in place:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct Bar {
+//!     name: String,
+//!     port: Option<u16>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "&'a mut Bar", unpack = true, make_refs = "mut")]
+//! struct Foo<'a> {
+//!     #[fromsuper(unpack = false)]
+//!     name: &'a mut String,
+//!     port: &'a mut u16,
+//! }
+//!
+//! let mut bar = Bar { name: "server".to_string(), port: Some(80) };
+//! let foo: Foo = (&mut bar).try_into().unwrap();
+//! *foo.port = 8080;
+//! assert_eq!(Some(8080), bar.port);
+//! ```
+//!
+//! If the sub struct should own its values while the super struct is kept (e.g. to extract
+//! several summaries from a large document), the values can be cloned from the borrowed
+//! super struct using `clone = true` (or `clone` for single fields).
+//! Unpacked fields only clone the value inside the `Option`:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct Bar {
+//!     name: Option<String>,
+//!     tags: Vec<String>,
+//!     data: Vec<u8>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "&Bar", unpack = true, clone = true)]
+//! struct Foo {
+//!     name: String,
+//!     #[fromsuper(unpack = false)]
+//!     tags: Vec<String>,
+//! }
+//!
+//! let bar = Bar {
+//!     name: Some("name".to_string()),
+//!     tags: vec!["tag".to_string()],
+//!     data: vec![1, 2, 3],
+//! };
+//! let foo = Foo::try_from(&bar).unwrap();
+//! assert_eq!("name", foo.name);
+//! assert_eq!(Some("name"), bar.name.as_deref());
+//! ```
+//!
+//! Fields of type `Cow<'a, T>` marked with `cow` can serve both, borrowed and owned super structs.
+//! Converting from a borrowed super struct (e.g. `&'a Bar`) makes them `Cow::Borrowed`,
+//! possibly in a borrowed form of the source value (e.g. `Cow<'a, str>` of a `String`),
+//! while converting from an owned one makes them `Cow::Owned`.
+//! This way, a single sub struct derives both conversions:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! use std::borrow::Cow;
+//!
+//! struct Bar {
+//!     name: Option<String>,
+//!     data: Vec<u8>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar", from_type = "&'a Bar", unpack = true)]
+//! struct Foo<'a> {
+//!     #[fromsuper(cow)]
+//!     name: Cow<'a, str>,
+//!     #[fromsuper(cow, unpack = false)]
+//!     data: Cow<'a, [u8]>,
+//! }
+//!
+//! let bar = Bar { name: Some("name".to_string()), data: vec![1, 2] };
+//! let foo = Foo::try_from(&bar).unwrap();
+//! assert!(matches!(foo.name, Cow::Borrowed("name")));
+//!
+//! let foo = Foo::try_from(bar).unwrap();
+//! assert!(matches!(foo.name, Cow::Owned(_)));
+//! ```
+//!
+//! When converting from an owned super struct, lifetimes of the sub struct that are only used
+//! by `cow` fields need not be known from the super struct, since these fields own their values.
+//!
+//! ## Tuple structs
+//!
+//! Both, the sub and the super struct, can also be tuple structs.
+//! Fields of tuple structs are taken from the same position in the super struct
+//! by default.
+//! In order to take them from another position,
+//! or when converting between tuple structs and normal structs,
+//! `rename_from` accepts the index of a super struct's field as well:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct Bar(u32, Option<String>, char);
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar", unpack = true)]
+//! struct Foo(
+//!     #[fromsuper(rename_from = "2", unpack = false)] char,
+//!     #[fromsuper(rename_from = "1")] String,
+//! );
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar")]
+//! struct FooNamed {
+//!     #[fromsuper(rename_from = "0")]
+//!     id: u32,
+//! }
+//! ```
+//!
+//! ## Converting enums
+//!
+//! `derive(FromSuper)` can also be used on enums, whose variants are a subset
+//! of the super enum's variants.
+//! The variants' fields are converted by the same rules as the fields of a struct,
+//! i.e., they can be renamed, unpacked or referenced.
+//! Variants can be taken from a differently-named super variant using `rename_from`.
+//!
+//! As the sub enum does not model all variants of the super enum,
+//! `TryFrom` is implemented, failing for any variant not modeled.
+//! In order for the error to name the encountered variant, the remaining variants
+//! have to be listed using `unmodeled_variants`.
+//! The compiler then makes sure that no variant is forgotten.
+//! Without this list, the variants of the super enum are unknown to the derive macro,
+//! so the error can only tell that some variant is not modeled.
+//! Alternatively, a single variant can be marked as the catch-all for all other variants
+//! using the `other` option.
+//! It can either have no fields at all, or a single one receiving the super value as is.
+//! In this case, `From` is implemented, unless unpacking is activated:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! #[derive(Debug)]
+//! enum Bar {
+//!     A(u32, Option<String>),
+//!     B { x: u8, y: char },
+//!     C,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar", unpack = true, unmodeled_variants = "C")]
+//! enum Foo {
+//!     A(#[fromsuper(unpack = false)] u32, String),
+//!     #[fromsuper(rename_from = "B")]
+//!     Renamed {
+//!         #[fromsuper(unpack = false)]
+//!         x: u8,
+//!     },
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar")]
+//! enum FooOther {
+//!     C,
+//!     #[fromsuper(other)]
+//!     Other(Bar),
+//! }
+//! ```
+//!
+//! ## Converting from multiple super types
+//!
+//! `from_type` can be specified multiple times,
+//! deriving a separate conversion for each of the given super types.
+//! Field options such as `rename_from` and `unpack` can then be given
+//! either for all super types at once, or separately for each of them.
+//! In the latter case, the super types are referred to by their name
+//! (or a trailing part of their path, if the name alone is ambiguous):
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct BarV1 {
+//!     id: Option<u32>,
+//!     name: String,
+//! }
+//!
+//! struct BarV2 {
+//!     identifier: Option<u32>,
+//!     name: Option<String>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "BarV1", from_type = "BarV2", unpack = true)]
+//! struct Foo {
+//!     #[fromsuper(rename_from(BarV2 = "identifier"))]
+//!     id: u32,
+//!     #[fromsuper(unpack(BarV1 = false))]
+//!     name: String,
+//! }
+//! ```
+//!
+//! The keys only consist of the path of the super type, without any references or generic
+//! arguments. Super types that only differ in these, such as `Bar` and `&'a Bar`, or
+//! `Bar<u8>` and `Bar<u16>`, can therefore not be told apart: a key such as `Bar`
+//! refers to all of them, so their options can only be given for all of them at once.
+//! Options naming generated items, i.e. `error` and `apply_to`, reject such keys,
+//! as the items would clash.
+//!
+//! ## Nested conversions
+//!
+//! Fields whose type has its own conversion from the super field's type,
+//! e.g. because it is derived using `FromSuper` as well,
+//! can be converted using the `nested` option.
+//! It is combined with unpacking, so an `Option<BarInner>` can be converted to a `FooInner`.
+//! As the inner conversion may fail, `TryFrom` is implemented for the outer struct.
+//! Its error reports all fields that could not be converted, not only the first one.
+//! Fields missing within the nested conversion are reported by their full path, e.g. `inner.a`:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct BarInner {
+//!     a: Option<u32>,
+//! }
+//!
+//! struct Bar {
+//!     inner: BarInner,
+//!     maybe_inner: Option<BarInner>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "BarInner", unpack = true)]
+//! struct FooInner {
+//!     a: u32,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar", unpack = true)]
+//! struct Foo {
+//!     #[fromsuper(nested, unpack = false)]
+//!     inner: FooInner,
+//!     #[fromsuper(nested)]
+//!     maybe_inner: FooInner,
+//! }
+//! ```
+//!
+//! Collections can be converted element by element using the `nested_elements` option,
+//! e.g. a `Vec<BarInner>` to a `Vec<FooInner>`. This works for `Vec`s, `VecDeque`s, arrays,
+//! `HashMap`s and `BTreeMap`s (whose values are converted), as well as for `Option`s of them.
+//! Failed elements are reported by their index or key, e.g. `items[3].a` or `by_name["x"].a`:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! # use std::collections::HashMap;
+//! # struct BarInner {
+//! #     a: Option<u32>,
+//! # }
+//! #
+//! # #[derive(FromSuper)]
+//! # #[fromsuper(from_type = "BarInner", unpack = true)]
+//! # struct FooInner {
+//! #     a: u32,
+//! # }
+//! #
+//! struct Bar {
+//!     items: Vec<BarInner>,
+//!     by_name: HashMap<String, BarInner>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar")]
+//! struct Foo {
+//!     #[fromsuper(nested_elements)]
+//!     items: Vec<FooInner>,
+//!     #[fromsuper(nested_elements)]
+//!     by_name: HashMap<String, FooInner>,
+//! }
+//! ```
+//!
+//! The elements of collections can also be unpacked using the `unpack_elements` option,
+//! e.g. to convert a `Vec<Option<T>>` to a `Vec<T>`. Missing elements are reported by their
+//! index or key, e.g. `column[3]`. It can be combined with `nested_elements` in order to
+//! convert the unpacked elements:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct Bar {
+//!     column: Vec<Option<u32>>,
+//!     fixed: [Option<char>; 2],
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar")]
+//! struct Foo {
+//!     #[fromsuper(unpack_elements)]
+//!     column: Vec<u32>,
+//!     #[fromsuper(unpack_elements)]
+//!     fixed: [char; 2],
+//! }
+//! ```
+//!
+//! ## Custom conversions
+//!
+//! If a field needs to be transformed on its way to the sub struct,
+//! a custom function can be given using `with`.
+//! It is called with the (unpacked) source value and returns the field's value.
+//! Conversions that can fail are given using `try_with` instead.
+//! Such a function returns a `Result` whose error can be converted
+//! into a `Box<dyn Error + Send + Sync>`.
+//! Its errors are collected together with those of missing fields:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct Bar {
+//!     name: Option<String>,
+//!     port: String,
+//! }
+//!
+//! fn parse_port(port: String) -> Result<u16, std::num::ParseIntError> {
+//!     port.parse()
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar", unpack = true)]
+//! struct Foo {
+//!     #[fromsuper(with = "String::into_bytes")]
+//!     name: Vec<u8>,
+//!     #[fromsuper(try_with = "parse_port", unpack = false)]
+//!     port: u16,
+//! }
+//! ```
+//!
+//! ## Default values for missing fields
+//!
+//! Instead of failing, unpacked fields can fall back to a default value if they are `None`.
+//! With the `default` option, the field type's `Default` implementation is used.
+//! Alternatively, an expression of the field's type can be given, e.g. `default = "42"`.
+//! The default value is used as it is, so it is neither converted (e.g. using `nested` or `with`)
+//! nor validated.
+//! To use default values for all unpacked fields, add `default_missing = true`
+//! to the struct:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct Bar {
+//!     id: Option<u32>,
+//!     tags: Option<Vec<String>>,
+//!     port: Option<u16>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar", unpack = true)]
+//! struct Foo {
+//!     id: u32,
+//!     #[fromsuper(default)]
+//!     tags: Vec<String>,
+//!     #[fromsuper(default = "80")]
+//!     port: u16,
+//! }
+//! ```
+//!
+//! ## Fields not taken from the super struct
+//!
+//! The sub struct can contain additional fields that do not exist in the super struct,
+//! e.g. caches or counters.
+//! Such fields are marked with `skip` and are initialized
+//! using their type's `Default` implementation,
+//! or the expression given using `default`.
+//! Fields of type `PhantomData` are skipped automatically:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! # use std::collections::HashMap;
+//! # use std::marker::PhantomData;
+//! struct Bar {
+//!     a: u32,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar")]
+//! struct Foo<T> {
+//!     a: u32,
+//!     #[fromsuper(skip)]
+//!     cache: HashMap<u32, String>,
+//!     #[fromsuper(skip, default = "1")]
+//!     counter: usize,
+//!     marker: PhantomData<T>,
+//! }
+//! ```
+//!
+//! ## Naming the error type
+//!
+//! By default, the error type of a fallible conversion is private
+//! and has a generated name.
+//! In order to use it e.g. in the signatures of public functions,
+//! it can be named using `error`, and made public using `error_vis`.
+//! Additional traits can be derived using `error_derive`,
+//! and further attributes can be added using `error_attr`.
+//! When converting from multiple super types,
+//! the error type needs to be named separately for each of them,
+//! e.g. `error(BarV1 = "FooV1Error", BarV2 = "FooV2Error")`:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! pub struct Bar {
+//!     a: Option<u32>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(
+//!     from_type = "Bar",
+//!     unpack = true,
+//!     error = "FooError",
+//!     error_vis = "pub",
+//!     error_derive = "Clone, Eq",
+//!     error_attr(non_exhaustive)
+//! )]
+//! pub struct Foo {
+//!     a: u32,
+//! }
+//!
+//! pub fn convert(bar: Bar) -> Result<Foo, FooError> {
+//!     bar.try_into()
+//! }
+//! ```
+//!
+//! As each generated error type needs a name of its own, a key naming it must not refer to
+//! several super types. This rules out naming the errors of super types that only differ in
+//! references or generic arguments, e.g. of `Bar` and `&'a Bar`:
+//!
+//! ```rust,compile_fail
+//! # use fromsuper::FromSuper;
+//! struct Bar {
+//!     a: Option<u32>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(
+//!     from_type = "Bar",
+//!     from_type = "&'a Bar",
+//!     unpack = true,
+//!     clone = true,
+//!     error(Bar = "FooError") // refers to both super types
+//! )]
+//! struct Foo {
+//!     a: u32,
+//! }
+//! ```
+//!
+//! ## Inspecting conversion errors
+//!
+//! Along with a fallible conversion, an enum named after the sub struct
+//! (e.g. `FooField` for `Foo`) is generated, with a variant for each of its fields.
+//! The variants are named after the fields in camel case, e.g. `SomeField` for `some_field`.
+//! For tuple structs, they are named by position, e.g. `Field0`,
+//! and for enums, they are prefixed with the name of the variant, e.g. `A0` or `BSomeField`.
+//! The enum can be named differently using `field_enum`, e.g. `field_enum = "FooPart"`,
+//! and it has the visibility given by `error_vis`, if any.
+//! The conversion error uses this enum to tell which fields were missing or could not be converted:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct Bar {
+//!     id: Option<u32>,
+//!     the_name: Option<String>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar", unpack = true)]
+//! struct Foo {
+//!     id: u32,
+//!     #[fromsuper(rename_from = "the_name")]
+//!     name: String,
+//! }
+//!
+//! let err = Foo::try_from(Bar { id: Some(1), the_name: None }).err().unwrap();
+//! assert!(err.is_missing(FooField::Name));
+//! assert_eq!(vec![FooField::Name], err.missing_fields().collect::<Vec<_>>());
+//! assert_eq!(vec!["the_name"], err.missing_sources().collect::<Vec<_>>());
+//! ```
+//!
+//! The error provides the following methods:
+//!
+//! - `missing_fields()` iterates over the fields that were `None` in the super struct.
+//! - `is_missing(field)` tells whether the given field was `None` in the super struct.
+//! - `missing_sources()` iterates over the names of the missing fields within the super struct.
+//! - `invalid_fields()` iterates over the fields that could not be converted,
+//!   if the conversion of any field can fail (e.g. because of `nested` or `try_with`).
+//! - `super_type_name()` returns the full name of the super type, including its generic arguments.
+//!
+//! All error types also implement the trait `fromsuper::ConversionError`.
+//! Its methods `missing_paths()` and `invalid_paths()` return the paths of the failed fields
+//! as `fromsuper::FieldPath`s, including those within nested conversions, e.g. `header.id`.
+//! The error's `source()` is the error of the first field that could not be converted,
+//! which allows following the chain of errors into nested conversions.
+//!
+//! ## Sharing the error type
+//!
+//! Instead of generating a separate error type for each conversion,
+//! all conversions can use the error type `fromsuper::MissingFields`
+//! by adding `shared_error = true`.
+//! This allows handling the errors of many different sub structs the same way.
+//! It provides the same methods as the generated error types,
+//! but refers to the fields of the sub struct by their names.
+//! Additionally, `sub_type_name()` returns the name of the sub type,
+//! and for enums, `variant()` and `is_unmodeled()` tell the variant
+//! of the super enum that failed to be converted:
+//!
+//! ```rust
+//! # use fromsuper::{FromSuper, MissingFields};
+//! struct Bar {
+//!     a: Option<u32>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar", unpack = true, shared_error = true)]
+//! struct Foo {
+//!     a: u32,
+//! }
+//!
+//! fn report(err: MissingFields) -> String {
+//!     format!("{}: {:?}", err.sub_type_name(), err.missing_fields().collect::<Vec<_>>())
+//! }
+//!
+//! let err = Foo::try_from(Bar { a: None }).err().unwrap();
+//! assert!(report(err).ends_with("Foo: [\"a\"]"));
+//! ```
+//!
+//! ## Using an existing error type
+//!
+//! A fallible conversion can also return an existing error type, e.g. the one of your application.
+//! It is given as a path using `error`, along with a function converting
+//! `fromsuper::MissingFields` to it using `map_err`.
+//! If `map_err` is given without a function,
+//! the error type's implementation of `From<fromsuper::MissingFields>` is used:
+//!
+//! ```rust
+//! # use fromsuper::{FromSuper, MissingFields};
+//! #[derive(Debug)]
+//! enum MyError {
+//!     Missing(Vec<&'static str>),
+//! }
+//!
+//! impl MyError {
+//!     fn from_missing(missing: MissingFields) -> Self {
+//!         MyError::Missing(missing.missing_fields().collect())
+//!     }
+//! }
+//!
+//! struct Bar {
+//!     a: Option<u32>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(
+//!     from_type = "Bar",
+//!     unpack = true,
+//!     error = "MyError",
+//!     map_err = "MyError::from_missing"
+//! )]
+//! struct Foo {
+//!     a: u32,
+//! }
+//!
+//! fn convert(bar: Bar) -> Result<Foo, MyError> {
+//!     bar.try_into()
+//! }
+//! ```
+//!
+//! ## Converting back into the super struct
+//!
+//! Using the option `into_super`, the conversion from the sub struct back into
+//! the super struct is derived as well, i.e. `From<Foo>` is implemented for `Bar`.
+//! Unpacked fields are packed again (e.g. wrapped in `Some`, see [`Pack::pack`]),
+//! and fields taken from differently-named (or nested) fields are converted back accordingly.
+//! The fields of the super struct that are not modeled by the sub struct are taken from
+//! its `Default` implementation, or from a base value given as an expression:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! #[derive(Default)]
+//! struct Bar {
+//!     a: Option<u32>,
+//!     b: Option<String>,
+//!     c: bool,
+//! }
+//!
+//! fn base() -> Bar {
+//!     Bar { c: true, ..Default::default() }
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar", unpack = true, into_super = "base()")]
+//! struct Foo {
+//!     #[fromsuper(rename_from = "b")]
+//!     name: String,
+//! }
+//!
+//! let bar = Bar::from(Foo { name: "name".to_string() });
+//! assert_eq!(Some("name".to_string()), bar.b);
+//! assert!(bar.c);
+//! ```
+//!
+//! Borrowed super types such as `&'a Bar` cannot be converted back into, so they are skipped
+//! if the sub struct is also converted from owned ones, e.g. from both `Bar` and `&'a Bar`.
+//!
+//! Instead of creating a new super struct, the sub struct's fields can also be written back
+//! into an existing one, using a method generated by the option `apply_to`.
+//! By default, it is called `apply_to`, but it can be named differently using
+//! `apply_to = "..."`. With multiple super types, a name needs to be given for each of them,
+//! e.g. `apply_to(BarV1 = "apply_to_v1", BarV2 = "apply_to_v2")`.
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct Bar {
+//!     a: Option<u32>,
+//!     b: Vec<u8>,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(from_type = "Bar", unpack = true, apply_to)]
+//! struct Foo {
+//!     a: u32,
+//! }
+//!
+//! let mut bar = Bar { a: None, b: vec![1, 2, 3] };
+//! Foo { a: 2 }.apply_to(&mut bar);
+//! assert_eq!(Some(2), bar.a);
+//! assert_eq!(vec![1, 2, 3], bar.b);
+//! ```
+//!
+//! Fields that are referenced or converted using `with` or `try_with` cannot be written back,
+//! so they need to be skipped.
+//!
+//! As each of the methods needs a name of its own, a key naming one must not refer to
+//! several super types. This rules out super types that only differ in their generic arguments:
+//!
+//! ```rust,compile_fail
+//! # use fromsuper::FromSuper;
+//! struct Bar<T> {
+//!     a: Option<u32>,
+//!     b: T,
+//! }
+//!
+//! #[derive(FromSuper)]
+//! #[fromsuper(
+//!     from_type = "Bar<u8>",
+//!     from_type = "Bar<u16>",
+//!     unpack = true,
+//!     apply_to(Bar = "write") // refers to both super types
+//! )]
+//! struct Foo {
+//!     a: u32,
+//! }
+//! ```
+//!
+//! ## Validating the converted values
+//!
+//! A field can be checked once it has been converted, using `validate`.
+//! The given function receives a reference to the field's value and returns
+//! a `Result<(), E>`, whose errors are collected like those of `try_with`.
+//! Given on the struct itself, `validate` checks the finished sub struct instead.
+//! It is only called if all fields were converted successfully,
+//! and its error is reported by the same error type:
+//!
+//! ```rust
+//! # use fromsuper::FromSuper;
+//! struct Bar {
+//!     name: Option<String>,
+//!     min: Option<u32>,
+//!     max: Option<u32>,
+//! }
+//!
+//! fn non_empty(name: &str) -> Result<(), &'static str> {
+//!     if name.is_empty() { Err("empty name") } else { Ok(()) }
+//! }
+//!
+//! fn valid_range(foo: &Foo) -> Result<(), &'static str> {
+//!     if foo.min <= foo.max { Ok(()) } else { Err("min exceeds max") }
+//! }
+//!
+//! #[derive(Debug, FromSuper)]
+//! #[fromsuper(from_type = "Bar", unpack = true, validate = "valid_range")]
+//! struct Foo {
+//!     #[fromsuper(validate = "non_empty")]
+//!     name: String,
+//!     min: u32,
+//!     max: u32,
+//! }
+//!
+//! let bar = Bar {
+//!     name: Some("foo".to_string()),
+//!     min: Some(3),
+//!     max: Some(2),
+//! };
+//! let err = Foo::try_from(bar).unwrap_err();
+//! assert_eq!("The converted Foo is invalid: min exceeds max", err.to_string());
+//! ```
+
+#[doc(hidden)]
+#[path = "private.rs"]
+pub mod __private;
+mod error;
+mod path;
+mod unpack;
+
+pub use error::{ConversionError, ElementErrors, MissingFields};
+pub use path::{FieldPath, PathSegment};
+pub use unpack::{Pack, Unpack};
 
 /// The procedural macro this crate is all about.
 ///
@@ -195,14 +978,46 @@
 ///
 /// The attribute that is used to configure the derive process,
 /// is named `fromsuper`.
-/// It can be applied to the whole struct as well as to individual fields.
+/// It can be applied to the whole struct (or enum) as well as to individual fields
+/// (and enum variants).
 /// It currently handles the following config options:
 ///
 /// | Config Option | Applied to... | Required | Data Type          | Description
 /// | ------------- | ------------- | -------- | ------------------ | ------------- |
-/// | `from_type`   | struct        | **yes**  | type specification | The type of the super struct to convert from. Must be enclosed in `"..."`. Can be a local type or fully qualified. Generic type parameters (not concrete types used for instantiation) need to be prefixed with a `#` symbol. |
-/// | `unpack`      | struct        | no       | bool               | Unpack each source field, assuming it is an `Option`. If unpacking is activated, `TryFrom` is implemented instead of `From`. |
-/// | `make_refs`   | struct        | no       | bool               | Instead of moving the field values to the sub struct, make references to the original values. This only really makes sense if `from_type` is a reference type (e.g. `&'a Bar`). |
+/// | `from_type`   | struct        | **yes**  | type specification | The type of the super struct to convert from. Must be enclosed in `"..."`. Can be a local type or fully qualified. Generic type parameters (not concrete types used for instantiation) need to be prefixed with a `#` symbol. Can be given multiple times to derive conversions from several super types. |
+/// | `unpack`      | struct        | no       | bool               | Unpack each source field, assuming it is an `Option` (or another type implementing `Unpack`). If unpacking is activated, `TryFrom` is implemented instead of `From`. |
+/// | `make_refs`   | struct        | no       | bool or `"mut"`    | Instead of moving the field values to the sub struct, make references to the original values. This only really makes sense if `from_type` is a reference type (e.g. `&'a Bar`). Fields can also be borrowed forms of the source values (e.g. `&'a str` or `Option<&'a str>` of a `String` or `Option<String>`). With `"mut"`, mutable references are made from a mutable reference type (e.g. `&'a mut Bar`). |
+/// | `clone`       | struct        | no       | bool               | Instead of moving the field values to the sub struct, clone them from the borrowed super struct, given as a reference type (e.g. `&Bar`). Unpacked fields only clone the value inside the `Option`. Cannot be combined with `make_refs`. |
+/// | `default_missing` | struct    | no       | bool               | Use the `Default` implementation for all unpacked fields that are `None`, instead of failing. |
+/// | `shared_error` | struct       | no       | bool               | Use `fromsuper::MissingFields` as the error type of a fallible conversion, instead of generating one. |
+/// | `error`       | struct        | no       | identifier or path | The name of the error type of a fallible conversion. Needs to be given for each super type separately if there are several. Together with `map_err`, the path of an existing error type. |
+/// | `map_err`     | struct        | no       | path (optional)    | The function converting `fromsuper::MissingFields` to the existing error type given by `error`. Without a function, the error type's `From` implementation is used. `error` needs to apply to every super type. |
+/// | `error_vis`   | struct        | no       | visibility         | The visibility of the error type, e.g. `"pub"`. It is private by default. |
+/// | `error_derive` | struct       | no       | list of paths      | Additional traits to derive for the error type, e.g. `"Clone, Eq"`. `Debug` and, if possible, `PartialEq` are always derived. |
+/// | `error_attr`  | struct        | no       | list of attributes | Additional attributes for the error type, e.g. `error_attr(non_exhaustive)`. A `doc` attribute replaces the generated documentation. |
+/// | `field_enum`  | struct        | no       | identifier         | The name of the enum referring to the fields in the generated error types. Defaults to the name of the sub type followed by `Field`, e.g. `FooField`. |
+/// | `into_super`  | struct        | no       | bool or expression | Also derive the conversion back into the super struct. Its remaining fields are taken from the given base value, or from its `Default` implementation. Borrowed super types are skipped if there are owned ones. |
+/// | `apply_to`    | struct        | no       | optional identifier | Generate a method (called `apply_to` by default) writing the fields back into an existing super struct. Needs to be named for each super type separately if there are several. |
+/// | `validate`    | struct        | no       | path               | Validate the converted sub struct using the given function, which receives a reference to it and returns a `Result<(), E>`. Its error is reported by the conversion error. |
+/// | `unmodeled_variants` | enum   | no       | list of identifiers | The variants of the super enum not modeled by the sub enum, e.g. `"C, D"`. Naming them makes the conversion error name the encountered variant. Can be given separately for each super type, e.g. `unmodeled_variants(BarV1 = "C", BarV2 = "D")`. |
 /// | `unpack`      | field         | no       | bool               | If false, do not unpack this field. |
-/// | `rename_from` | field         | no       | identifier         | Use a differently-named field as the source from the super struct. |
+/// | `unpack_depth` | field        | no       | integer            | The number of layers to unpack, e.g. 2 for an `Option<Option<T>>`. Defaults to 1. |
+/// | `unpack_result` | field       | no       | bool               | Unpack the source value as a `Result` instead of an `Option`. Its error is kept in the conversion error, which requires it to implement `Clone` when converting from a borrowed super struct. |
+/// | `rename_from` | field         | no       | identifier or index | Use a differently-named field as the source from the super struct. For tuple structs, the index of the source field can be given instead (e.g. `"2"`). |
+/// | `nested`      | field         | no       | bool               | Convert the (unpacked) source value using the `TryFrom` implementation of this field's type. Errors of the inner conversion are collected in the error of the outer one. |
+/// | `nested_elements` | field     | no       | bool               | Convert the (unpacked) source collection element by element, using the `TryFrom` implementation of this field's element type. Failed elements are reported by their index or key. |
+/// | `unpack_elements` | field     | no       | bool               | Unpack the elements of the source collection, e.g. of a `Vec<Option<T>>`. Missing elements are reported by their index or key. |
+/// | `with`        | field         | no       | path               | Convert the (unpacked) source value using the given function. |
+/// | `try_with`    | field         | no       | path               | Convert the (unpacked) source value using the given function returning a `Result`. Its errors are collected in the conversion error. |
+/// | `validate`    | field         | no       | path               | Validate the converted value using the given function, which receives a reference to it and returns a `Result<(), E>`. Its errors are collected in the conversion error. |
+/// | `default`     | field         | no       | bool or expression | Use a default value if the unpacked source value is `None`, or for skipped fields. Without an expression, the field type's `Default` implementation is used. The default value is neither converted nor validated. |
+/// | `skip`        | field         | no       | bool               | Do not take this field from the super struct, but initialize it with its default value. `PhantomData` fields are skipped automatically. |
+/// | `make_ref`    | field         | no       | bool or `"mut"`    | Make a (mutable, with `"mut"`) reference to the original value, or copy it if false. Overrides the struct-level `make_refs` for this field. |
+/// | `clone`       | field         | no       | bool               | Clone the value from the borrowed super struct, or copy it if false. Overrides the struct-level `clone` and `make_refs` for this field. |
+/// | `cow`         | field         | no       | bool               | Take the value of this `Cow` field as `Cow::Borrowed` from a borrowed super struct (e.g. `&'a Bar`), or as `Cow::Owned` from an owned one. |
+/// | `rename_from` | variant       | no       | identifier         | Use a differently-named variant of the super enum as the source. |
+/// | `other`       | variant       | no       | bool               | Convert all super variants not modeled otherwise to this variant. It can have a single field, which receives the super value. |
+///
+/// All field options can also be given separately for each super type,
+/// e.g. `rename_from(BarV1 = "x", BarV2 = "y")`.
 pub use fromsuper_macros::FromSuper;
@@ -0,0 +1,52 @@
+//! Paths to fields within nested conversions
+
+use std::fmt;
+
+/// A single step of a [`FieldPath`]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum PathSegment {
+    /// A named field, or the position of a field within a tuple struct
+    Field(&'static str),
+
+    /// An element of a list, such as a `Vec`
+    Index(usize),
+
+    /// An element of a map, given by the `Debug` representation of its key
+    Key(String),
+}
+
+/// The path to a field within nested conversions, such as `items[3].header.id`
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FieldPath(Vec<PathSegment>);
+
+impl FieldPath {
+    #[doc(hidden)]
+    pub fn new(segment: PathSegment) -> Self {
+        FieldPath(vec![segment])
+    }
+
+    #[doc(hidden)]
+    pub fn prefixed(mut self, segment: PathSegment) -> Self {
+        self.0.insert(0, segment);
+        self
+    }
+
+    /// The steps leading to the field, starting at the outermost one.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.0
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Field(name) if i == 0 => write!(f, "{}", name)?,
+                PathSegment::Field(name) => write!(f, ".{}", name)?,
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+                PathSegment::Key(key) => write!(f, "[{}]", key)?,
+            }
+        }
+        Ok(())
+    }
+}
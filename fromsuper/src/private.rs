@@ -0,0 +1,380 @@
+//! Helpers for the code generated by the derive macro, not part of the public API
+
+use std::borrow::{Borrow, BorrowMut};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::hash::{BuildHasher, Hash};
+
+use crate::{ConversionError, ElementErrors, FieldPath, PathSegment};
+
+/// The reason a field could not be converted
+pub enum Invalid {
+    /// An arbitrary error, e.g. returned by a `try_with` function
+    Opaque(Box<dyn Error + Send + Sync>),
+
+    /// The error of a nested conversion, whose failed fields are reported as well
+    Nested(Box<dyn ConversionError>),
+}
+
+impl Invalid {
+    pub fn error(&self) -> &(dyn Error + 'static) {
+        match self {
+            Invalid::Opaque(error) => &**error,
+            Invalid::Nested(error) => &**error,
+        }
+    }
+}
+
+impl fmt::Debug for Invalid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Invalid::Opaque(error) => fmt::Debug::fmt(error, f),
+            Invalid::Nested(error) => fmt::Debug::fmt(error, f),
+        }
+    }
+}
+
+// Tell errors of nested conversions apart from other errors, using autoref-based
+// specialization: `(&error).fromsuper_kind().invalid(error)` resolves to `NestedKind`
+// if the error implements `ConversionError`, and to `OpaqueKind` otherwise.
+
+pub struct NestedTag;
+
+pub trait NestedKind {
+    fn fromsuper_kind(&self) -> NestedTag {
+        NestedTag
+    }
+}
+
+impl<E: ConversionError> NestedKind for E {}
+
+impl NestedTag {
+    pub fn invalid<E: ConversionError>(self, error: E) -> Invalid {
+        Invalid::Nested(Box::new(error))
+    }
+}
+
+pub struct OpaqueTag;
+
+pub trait OpaqueKind {
+    fn fromsuper_kind(&self) -> OpaqueTag {
+        OpaqueTag
+    }
+}
+
+impl<E: Into<Box<dyn Error + Send + Sync>>> OpaqueKind for &E {}
+
+impl OpaqueTag {
+    pub fn invalid<E: Into<Box<dyn Error + Send + Sync>>>(self, error: E) -> Invalid {
+        Invalid::Opaque(error.into())
+    }
+}
+
+/// Write the description of a conversion whose result failed validation.
+pub fn fmt_validation_error(
+    f: &mut fmt::Formatter<'_>,
+    sub_type: &str,
+    validation: &Invalid,
+) -> fmt::Result {
+    write!(
+        f,
+        "The converted {} is invalid: {}",
+        sub_type,
+        validation.error()
+    )
+}
+
+/// Borrow the value of an `Option`, possibly in a borrowed form (e.g. `&str` of a `String`).
+pub fn option_ref<S: Borrow<U>, U: ?Sized>(value: &Option<S>) -> Option<&U> {
+    value.as_ref().map(Borrow::borrow)
+}
+
+/// Borrow the value of an `Option` mutably, possibly in a borrowed form (e.g. `&mut [T]` of a `Vec<T>`).
+pub fn option_mut<S: BorrowMut<U>, U: ?Sized>(value: &mut Option<S>) -> Option<&mut U> {
+    value.as_mut().map(BorrowMut::borrow_mut)
+}
+
+/// Collect the paths of all missing fields, including those within nested conversions.
+pub fn missing_paths<'a>(
+    missing: impl IntoIterator<Item = &'static str>,
+    invalid: impl IntoIterator<Item = (&'static str, &'a Invalid)>,
+) -> Vec<FieldPath> {
+    missing_segment_paths(
+        missing.into_iter().map(PathSegment::Field),
+        invalid
+            .into_iter()
+            .map(|(field, invalid)| (PathSegment::Field(field), invalid)),
+    )
+}
+
+/// Collect the paths of all fields that could not be converted, along with their errors.
+/// Nested conversions that failed without any failed field (e.g. because of an enum variant
+/// that is not modeled) are reported as a whole.
+pub fn invalid_paths<'a>(
+    invalid: impl IntoIterator<Item = (&'static str, &'a Invalid)>,
+) -> Vec<(FieldPath, &'a (dyn Error + 'static))> {
+    invalid_segment_paths(
+        invalid
+            .into_iter()
+            .map(|(field, invalid)| (PathSegment::Field(field), invalid)),
+    )
+}
+
+/// Like [`missing_paths`], but for fields or elements given by arbitrary path segments.
+pub fn missing_segment_paths<'a>(
+    missing: impl IntoIterator<Item = PathSegment>,
+    invalid: impl IntoIterator<Item = (PathSegment, &'a Invalid)>,
+) -> Vec<FieldPath> {
+    let mut paths: Vec<FieldPath> = missing.into_iter().map(FieldPath::new).collect();
+
+    for (segment, invalid) in invalid {
+        if let Invalid::Nested(error) = invalid {
+            paths.extend(
+                error
+                    .missing_paths()
+                    .into_iter()
+                    .map(|path| path.prefixed(segment.clone())),
+            );
+        }
+    }
+
+    paths
+}
+
+/// Like [`invalid_paths`], but for fields or elements given by arbitrary path segments.
+pub fn invalid_segment_paths<'a>(
+    invalid: impl IntoIterator<Item = (PathSegment, &'a Invalid)>,
+) -> Vec<(FieldPath, &'a (dyn Error + 'static))> {
+    let mut paths = Vec::new();
+
+    for (segment, invalid) in invalid {
+        match invalid {
+            Invalid::Opaque(error) => paths.push((FieldPath::new(segment), &**error as &dyn Error)),
+            Invalid::Nested(error) => {
+                let nested = error.invalid_paths();
+                if nested.is_empty() && error.missing_paths().is_empty() {
+                    paths.push((FieldPath::new(segment.clone()), invalid.error()));
+                }
+                paths.extend(
+                    nested
+                        .into_iter()
+                        .map(|(path, error)| (path.prefixed(segment.clone()), error)),
+                );
+            }
+        }
+    }
+
+    paths
+}
+
+/// Write the description of a failed conversion, given the part of the super type
+/// that the fields belong to (e.g. `the super struct Bar`).
+pub fn fmt_error(
+    f: &mut fmt::Formatter<'_>,
+    super_part: &str,
+    missing: &[FieldPath],
+    invalid: &[(FieldPath, &(dyn Error + 'static))],
+) -> fmt::Result {
+    if !missing.is_empty() {
+        write!(f, "Attribute(s) ")?;
+
+        for (i, path) in missing.iter().enumerate() {
+            write!(f, "{}", path)?;
+            if i + 1 < missing.len() {
+                write!(f, ", ")?;
+            }
+        }
+
+        write!(f, " of {} not initialized", super_part)?;
+    }
+
+    for (i, (path, error)) in invalid.iter().enumerate() {
+        if i > 0 || !missing.is_empty() {
+            write!(f, "; ")?;
+        }
+        write!(
+            f,
+            "Attribute {} of {} could not be converted: {}",
+            path, super_part, error
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The reason an element of a collection could not be converted
+pub enum ElementFailure {
+    /// The element could not be unpacked
+    Missing,
+
+    /// The element could not be converted
+    Invalid(Invalid),
+}
+
+/// A collection of elements (or of values, for maps)
+pub trait Elements {
+    /// The type of the elements
+    type Item;
+}
+
+/// A collection that can be converted element by element from another one,
+/// such as a `Vec<T>` from a `Vec<S>`.
+pub trait FromElements<Source>: Elements + Sized {
+    /// The type of the source elements
+    type SourceItem;
+
+    /// Convert all elements using the given function, collecting the failures of all
+    /// elements.
+    fn from_elements(
+        source: Source,
+        convert: impl FnMut(Self::SourceItem) -> Result<Self::Item, ElementFailure>,
+    ) -> Result<Self, ElementErrors>;
+}
+
+/// Convert the elements of a list, referring to failed elements by their index.
+fn from_list_elements<S, T, C: FromIterator<T>>(
+    source: impl IntoIterator<Item = S>,
+    mut convert: impl FnMut(S) -> Result<T, ElementFailure>,
+) -> Result<C, ElementErrors> {
+    let mut errors = ElementErrors::new();
+    let converted = source
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, element)| match convert(element) {
+            Ok(element) => Some(element),
+            Err(failure) => {
+                errors.push(PathSegment::Index(index), failure);
+                None
+            }
+        })
+        .collect();
+
+    if errors.any_failed() {
+        Err(errors)
+    } else {
+        Ok(converted)
+    }
+}
+
+/// Convert the values of a map, referring to failed values by their key.
+fn from_map_values<K: fmt::Debug, S, T, C: FromIterator<(K, T)>>(
+    source: impl IntoIterator<Item = (K, S)>,
+    mut convert: impl FnMut(S) -> Result<T, ElementFailure>,
+) -> Result<C, ElementErrors> {
+    let mut errors = ElementErrors::new();
+    let converted = source
+        .into_iter()
+        .filter_map(|(key, value)| match convert(value) {
+            Ok(value) => Some((key, value)),
+            Err(failure) => {
+                errors.push(PathSegment::Key(format!("{:?}", key)), failure);
+                None
+            }
+        })
+        .collect();
+
+    if errors.any_failed() {
+        Err(errors)
+    } else {
+        Ok(converted)
+    }
+}
+
+impl<T> Elements for Vec<T> {
+    type Item = T;
+}
+
+impl<S, T> FromElements<Vec<S>> for Vec<T> {
+    type SourceItem = S;
+
+    fn from_elements(
+        source: Vec<S>,
+        convert: impl FnMut(S) -> Result<T, ElementFailure>,
+    ) -> Result<Self, ElementErrors> {
+        from_list_elements(source, convert)
+    }
+}
+
+impl<T> Elements for VecDeque<T> {
+    type Item = T;
+}
+
+impl<S, T> FromElements<VecDeque<S>> for VecDeque<T> {
+    type SourceItem = S;
+
+    fn from_elements(
+        source: VecDeque<S>,
+        convert: impl FnMut(S) -> Result<T, ElementFailure>,
+    ) -> Result<Self, ElementErrors> {
+        from_list_elements(source, convert)
+    }
+}
+
+impl<T, const N: usize> Elements for [T; N] {
+    type Item = T;
+}
+
+impl<S, T, const N: usize> FromElements<[S; N]> for [T; N] {
+    type SourceItem = S;
+
+    fn from_elements(
+        source: [S; N],
+        convert: impl FnMut(S) -> Result<T, ElementFailure>,
+    ) -> Result<Self, ElementErrors> {
+        let converted: Vec<T> = from_list_elements(source, convert)?;
+        Ok(match converted.try_into() {
+            Ok(converted) => converted,
+            Err(_) => unreachable!("all {} elements are converted", N),
+        })
+    }
+}
+
+impl<K, T, H> Elements for HashMap<K, T, H> {
+    type Item = T;
+}
+
+impl<K: Eq + Hash + fmt::Debug, S, T, H: BuildHasher + Default> FromElements<HashMap<K, S, H>>
+    for HashMap<K, T, H>
+{
+    type SourceItem = S;
+
+    fn from_elements(
+        source: HashMap<K, S, H>,
+        convert: impl FnMut(S) -> Result<T, ElementFailure>,
+    ) -> Result<Self, ElementErrors> {
+        from_map_values(source, convert)
+    }
+}
+
+impl<K, T> Elements for BTreeMap<K, T> {
+    type Item = T;
+}
+
+impl<K: Ord + fmt::Debug, S, T> FromElements<BTreeMap<K, S>> for BTreeMap<K, T> {
+    type SourceItem = S;
+
+    fn from_elements(
+        source: BTreeMap<K, S>,
+        convert: impl FnMut(S) -> Result<T, ElementFailure>,
+    ) -> Result<Self, ElementErrors> {
+        from_map_values(source, convert)
+    }
+}
+
+impl<C: Elements> Elements for Option<C> {
+    type Item = C::Item;
+}
+
+impl<S, C: FromElements<S>> FromElements<Option<S>> for Option<C> {
+    type SourceItem = C::SourceItem;
+
+    fn from_elements(
+        source: Option<S>,
+        convert: impl FnMut(C::SourceItem) -> Result<C::Item, ElementFailure>,
+    ) -> Result<Self, ElementErrors> {
+        source
+            .map(|source| C::from_elements(source, convert))
+            .transpose()
+    }
+}
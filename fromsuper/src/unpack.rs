@@ -0,0 +1,115 @@
+//! Unpacking the values of super struct fields
+
+/// A field value of the super struct that can be unpacked, i.e. it either holds a value
+/// or is missing.
+///
+/// The derived conversions use this trait to unpack field values, so implementing it
+/// allows unpacking custom wrapper types. It is implemented for `Option<T>`
+/// (including `Option<Box<T>>`, which is unpacked to `Box<T>`), and for `Box<Option<T>>`:
+///
+/// ```rust
+/// use fromsuper::{FromSuper, Unpack};
+///
+/// struct Lazy<T>(Option<T>);
+///
+/// impl<T> Unpack for Lazy<T> {
+///     type Inner = T;
+///
+///     fn unpack(self) -> Option<T> {
+///         self.0
+///     }
+///
+///     fn unpack_ref(&self) -> Option<&T> {
+///         self.0.as_ref()
+///     }
+///
+///     fn unpack_mut(&mut self) -> Option<&mut T> {
+///         self.0.as_mut()
+///     }
+/// }
+///
+/// struct Bar {
+///     a: Lazy<u32>,
+/// }
+///
+/// #[derive(FromSuper)]
+/// #[fromsuper(from_type = "Bar", unpack = true)]
+/// struct Foo {
+///     a: u32,
+/// }
+///
+/// let foo = Foo::try_from(Bar { a: Lazy(Some(1)) }).unwrap();
+/// assert_eq!(1, foo.a);
+/// ```
+pub trait Unpack {
+    /// The type of the unpacked value
+    type Inner;
+
+    /// Whether a value is present, i.e. unpacking it succeeds.
+    fn is_present(&self) -> bool {
+        self.unpack_ref().is_some()
+    }
+
+    /// Unpack the value, or get `None` if it is missing.
+    fn unpack(self) -> Option<Self::Inner>;
+
+    /// Unpack a reference to the value, or get `None` if it is missing.
+    fn unpack_ref(&self) -> Option<&Self::Inner>;
+
+    /// Unpack a mutable reference to the value, or get `None` if it is missing.
+    fn unpack_mut(&mut self) -> Option<&mut Self::Inner>;
+}
+
+/// A field value of the super struct that can be packed again after unpacking it.
+///
+/// This is only needed when converting back into the super struct
+/// using `into_super` or `apply_to`, which wrap the unpacked values again.
+/// It is implemented for the same types as [`Unpack`].
+pub trait Pack: Unpack {
+    /// Wrap an unpacked value again.
+    fn pack(inner: Self::Inner) -> Self;
+}
+
+impl<T> Unpack for Option<T> {
+    type Inner = T;
+
+    fn unpack(self) -> Option<T> {
+        self
+    }
+
+    fn unpack_ref(&self) -> Option<&T> {
+        self.as_ref()
+    }
+
+    fn unpack_mut(&mut self) -> Option<&mut T> {
+        self.as_mut()
+    }
+}
+
+impl<T> Pack for Option<T> {
+    fn pack(inner: T) -> Self {
+        Some(inner)
+    }
+}
+
+impl<T> Unpack for Box<Option<T>> {
+    type Inner = T;
+
+    fn unpack(self) -> Option<T> {
+        *self
+    }
+
+    fn unpack_ref(&self) -> Option<&T> {
+        self.as_ref().as_ref()
+    }
+
+    fn unpack_mut(&mut self) -> Option<&mut T> {
+        self.as_mut().as_mut()
+    }
+}
+
+impl<T> Pack for Box<Option<T>> {
+    fn pack(inner: T) -> Self {
+        Box::new(Some(inner))
+    }
+}
@@ -28,3 +28,17 @@ fn anyhow_convert() {
     assert!(anyhow_convert_inner_1().is_ok());
     assert!(anyhow_convert_inner_2().is_err());
 }
+
+#[derive(FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true, error = "anyhow::Error", map_err)]
+struct FooAnyhow {
+    #[allow(dead_code)]
+    x: Vec<u32>,
+}
+
+#[test]
+fn anyhow_error_type() {
+    let err: anyhow::Error = FooAnyhow::try_from(Bar { x: None }).err().unwrap();
+    let missing = err.downcast_ref::<fromsuper::MissingFields>().unwrap();
+    assert!(missing.is_missing("x"));
+}
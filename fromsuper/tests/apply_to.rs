@@ -0,0 +1,71 @@
+use fromsuper::FromSuper;
+
+#[derive(Debug, PartialEq)]
+struct Bar<T> {
+    a: Option<u32>,
+    b: Option<String>,
+    c: Vec<T>,
+    d: bool,
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "Bar<#T>", unpack = true, apply_to)]
+struct Foo {
+    a: u32,
+    #[fromsuper(rename_from = "b")]
+    name: String,
+}
+
+#[test]
+fn test_apply_to() {
+    let mut bar = Bar {
+        a: Some(1),
+        b: None,
+        c: vec![1, 2],
+        d: true,
+    };
+
+    let mut foo = Foo {
+        a: 2,
+        name: "name".to_string(),
+    };
+    foo.a += 1;
+    foo.apply_to(&mut bar);
+
+    assert_eq!(
+        Bar {
+            a: Some(3),
+            b: Some("name".to_string()),
+            c: vec![1, 2],
+            d: true,
+        },
+        bar
+    );
+}
+
+struct BarV1 {
+    a: u32,
+}
+
+struct BarV2 {
+    value: u32,
+}
+
+#[derive(FromSuper)]
+#[fromsuper(from_type = "BarV1", from_type = "BarV2")]
+#[fromsuper(apply_to(BarV1 = "apply_to_v1", BarV2 = "apply_to_v2"))]
+struct FooMultiple {
+    #[fromsuper(rename_from(BarV2 = "value"))]
+    a: u32,
+}
+
+#[test]
+fn test_apply_to_multiple() {
+    let mut v1 = BarV1 { a: 0 };
+    FooMultiple { a: 1 }.apply_to_v1(&mut v1);
+    assert_eq!(1, v1.a);
+
+    let mut v2 = BarV2 { value: 0 };
+    FooMultiple { a: 2 }.apply_to_v2(&mut v2);
+    assert_eq!(2, v2.value);
+}
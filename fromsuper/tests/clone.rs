@@ -0,0 +1,143 @@
+use fromsuper::FromSuper;
+
+#[derive(Clone, Debug, PartialEq)]
+struct ParseError;
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "could not parse the version")
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Clone)]
+struct Header {
+    id: Option<u32>,
+    title: Option<String>,
+}
+
+struct Document {
+    header: Header,
+    tags: Option<Vec<String>>,
+    body: String,
+    version: Result<u16, ParseError>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Header", unpack = true)]
+struct HeaderSummary {
+    id: u32,
+    title: String,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&Document", unpack = true, clone = true)]
+struct Summary {
+    #[fromsuper(nested, unpack = false)]
+    header: HeaderSummary,
+    tags: Vec<String>,
+    #[fromsuper(unpack_result)]
+    version: u16,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a Document", unpack = true, make_refs = true)]
+struct Preview<'a> {
+    #[fromsuper(unpack = false)]
+    body: &'a str,
+    #[fromsuper(clone)]
+    tags: Vec<String>,
+}
+
+fn document() -> Document {
+    Document {
+        header: Header {
+            id: Some(1),
+            title: Some("title".to_string()),
+        },
+        tags: Some(vec!["a".to_string()]),
+        body: "body".to_string(),
+        version: Ok(2),
+    }
+}
+
+#[test]
+fn test_clone() {
+    let doc = document();
+
+    // the document is kept, so that several summaries can be extracted
+    for _ in 0..2 {
+        let summary = Summary::try_from(&doc).unwrap();
+        assert_eq!(
+            Summary {
+                header: HeaderSummary {
+                    id: 1,
+                    title: "title".to_string(),
+                },
+                tags: vec!["a".to_string()],
+                version: 2,
+            },
+            summary
+        );
+    }
+
+    let doc = Document {
+        header: Header {
+            id: None,
+            title: Some("title".to_string()),
+        },
+        tags: None,
+        version: Err(ParseError),
+        ..document()
+    };
+    let err = Summary::try_from(&doc).unwrap_err();
+    assert_eq!(
+        vec![SummaryField::Tags],
+        err.missing_fields().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![SummaryField::Header, SummaryField::Version],
+        err.invalid_fields().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_clone_per_field() {
+    let doc = document();
+    let preview: Preview = (&doc).try_into().unwrap();
+    assert_eq!(
+        Preview {
+            body: "body",
+            tags: vec!["a".to_string()],
+        },
+        preview
+    );
+}
+
+enum Shape {
+    Circle { radius: Option<f64> },
+    Polygon(Vec<(f64, f64)>),
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a Shape", unpack = true, clone = true)]
+enum ShapeCopy {
+    Circle { radius: f64 },
+    Polygon(#[fromsuper(unpack = false)] Vec<(f64, f64)>),
+}
+
+#[test]
+fn test_clone_enum() {
+    let shape = Shape::Polygon(vec![(0.0, 1.0)]);
+    assert_eq!(
+        ShapeCopy::Polygon(vec![(0.0, 1.0)]),
+        ShapeCopy::try_from(&shape).unwrap()
+    );
+
+    let shape = Shape::Circle { radius: Some(2.0) };
+    assert_eq!(
+        ShapeCopy::Circle { radius: 2.0 },
+        ShapeCopy::try_from(&shape).unwrap()
+    );
+}
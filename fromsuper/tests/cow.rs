@@ -0,0 +1,132 @@
+use std::borrow::Cow;
+
+use fromsuper::FromSuper;
+
+#[derive(Debug, PartialEq)]
+struct Bar {
+    id: u32,
+    name: Option<String>,
+    data: Vec<u8>,
+    comment: Option<String>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", from_type = "&'a Bar", unpack = true)]
+struct Foo<'a> {
+    #[fromsuper(unpack = false)]
+    id: u32,
+    #[fromsuper(cow)]
+    name: Cow<'a, str>,
+    #[fromsuper(cow, unpack = false)]
+    data: Cow<'a, [u8]>,
+    #[fromsuper(cow, default = "Cow::Borrowed(\"none\")")]
+    comment: Cow<'a, str>,
+}
+
+fn bar() -> Bar {
+    Bar {
+        id: 1,
+        name: Some("name".to_string()),
+        data: vec![2, 3],
+        comment: None,
+    }
+}
+
+#[test]
+fn test_cow_borrowed() {
+    let bar = bar();
+    let foo = Foo::try_from(&bar).unwrap();
+    assert!(matches!(foo.name, Cow::Borrowed("name")));
+    assert!(matches!(foo.data, Cow::Borrowed(&[2, 3])));
+    assert!(matches!(foo.comment, Cow::Borrowed("none")));
+    assert_eq!(1, foo.id);
+}
+
+#[test]
+fn test_cow_owned() {
+    let foo = Foo::try_from(bar()).unwrap();
+    assert!(matches!(foo.name, Cow::Owned(ref name) if name == "name"));
+    assert!(matches!(foo.data, Cow::Owned(ref data) if data == &[2, 3]));
+
+    let err = Foo::try_from(Bar {
+        name: None,
+        ..bar()
+    })
+    .unwrap_err();
+    assert!(err.is_missing(FooField::Name));
+}
+
+#[derive(FromSuper)]
+#[fromsuper(from_type = "Bar", into_super = "bar()")]
+struct Data<'a> {
+    #[fromsuper(cow)]
+    data: Cow<'a, [u8]>,
+}
+
+#[test]
+fn test_cow_into_super() {
+    let data = Data {
+        data: Cow::Borrowed(&[4]),
+    };
+    assert_eq!(
+        Bar {
+            data: vec![4],
+            ..bar()
+        },
+        data.into()
+    );
+}
+
+enum Message {
+    Text(String),
+    Ping,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Message", from_type = "&'a Message")]
+enum MessageView<'a> {
+    Text(#[fromsuper(cow)] Cow<'a, str>),
+    Ping,
+}
+
+#[test]
+fn test_cow_enum() {
+    let message = Message::Text("hi".to_string());
+    assert!(matches!(
+        MessageView::try_from(&message).unwrap(),
+        MessageView::Text(Cow::Borrowed("hi"))
+    ));
+    assert!(matches!(
+        MessageView::try_from(message).unwrap(),
+        MessageView::Text(Cow::Owned(_))
+    ));
+    assert_eq!(
+        MessageView::Ping,
+        MessageView::try_from(Message::Ping).unwrap()
+    );
+}
+
+struct Entry<'b> {
+    key: &'b str,
+    value: Option<String>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Entry<'b>", unpack = true)]
+struct Pair<'a, 'b> {
+    #[fromsuper(unpack = false)]
+    key: &'b str,
+    #[fromsuper(cow)]
+    value: Cow<'a, str>,
+}
+
+#[test]
+fn test_cow_lifetimes() {
+    let pair = Pair::try_from(Entry {
+        key: "key",
+        value: Some("value".to_string()),
+    })
+    .unwrap();
+    assert_eq!("key", pair.key);
+    assert!(matches!(pair.value, Cow::Owned(ref value) if value == "value"));
+}
@@ -0,0 +1,115 @@
+use fromsuper::{FromSuper, MissingFields};
+
+#[derive(Debug, PartialEq)]
+enum MyError {
+    Missing(Vec<&'static str>),
+    Other,
+}
+
+impl MyError {
+    fn from_missing(missing: MissingFields) -> Self {
+        MyError::Missing(missing.missing_fields().collect())
+    }
+}
+
+impl From<MissingFields> for MyError {
+    fn from(_: MissingFields) -> Self {
+        MyError::Other
+    }
+}
+
+struct Bar {
+    a: Option<u32>,
+    b: Option<String>,
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(
+    from_type = "Bar",
+    unpack = true,
+    error = "crate::MyError",
+    map_err = "MyError::from_missing"
+)]
+struct Foo {
+    #[allow(dead_code)]
+    a: u32,
+    #[allow(dead_code)]
+    b: String,
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true, error = "MyError", map_err)]
+struct FooFrom {
+    #[allow(dead_code)]
+    a: u32,
+}
+
+fn convert(bar: Bar) -> Result<Foo, MyError> {
+    bar.try_into()
+}
+
+#[test]
+fn test_custom_error() {
+    assert_eq!(
+        MyError::Missing(vec!["a", "b"]),
+        convert(Bar { a: None, b: None }).unwrap_err()
+    );
+    assert_eq!(
+        MyError::Other,
+        FooFrom::try_from(Bar { a: None, b: None }).unwrap_err()
+    );
+}
+
+enum BarEnum {
+    A(u8),
+    B,
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(
+    from_type = "BarEnum",
+    unmodeled_variants = "B",
+    error = "MyError",
+    map_err = "MyError::from_missing"
+)]
+enum FooEnum {
+    A(#[allow(dead_code)] u8),
+}
+
+#[test]
+fn test_custom_error_enum() {
+    assert!(FooEnum::try_from(BarEnum::A(1)).is_ok());
+    assert_eq!(
+        MyError::Missing(vec![]),
+        FooEnum::try_from(BarEnum::B).unwrap_err()
+    );
+}
+
+struct BarV2 {
+    a: Option<u32>,
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(
+    from_type = "Bar",
+    from_type = "BarV2",
+    unpack = true,
+    error(Bar = "MyError", BarV2 = "MyError"),
+    map_err = "MyError::from_missing"
+)]
+struct FooMultiple {
+    #[allow(dead_code)]
+    a: u32,
+}
+
+#[test]
+fn test_custom_error_multiple() {
+    assert_eq!(
+        MyError::Missing(vec!["a"]),
+        FooMultiple::try_from(Bar { a: None, b: None }).unwrap_err()
+    );
+    assert_eq!(
+        MyError::Missing(vec!["a"]),
+        FooMultiple::try_from(BarV2 { a: None }).unwrap_err()
+    );
+}
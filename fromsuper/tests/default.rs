@@ -0,0 +1,161 @@
+use fromsuper::FromSuper;
+
+#[derive(Debug, Clone, Default)]
+struct Bar {
+    id: Option<u32>,
+    tags: Option<Vec<String>>,
+    name: Option<String>,
+    port: Option<String>,
+}
+
+fn default_port() -> u16 {
+    80
+}
+
+fn parse_port(port: String) -> Result<u16, std::num::ParseIntError> {
+    port.parse()
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true)]
+struct Foo {
+    id: u32,
+    #[fromsuper(default)]
+    tags: Vec<String>,
+    #[fromsuper(default = "\"unnamed\".to_string()")]
+    name: String,
+    #[fromsuper(default = "default_port()", try_with = "parse_port")]
+    port: u16,
+}
+
+#[test]
+fn test_default() {
+    assert_eq!(
+        Foo {
+            id: 1,
+            tags: vec![],
+            name: "unnamed".to_string(),
+            port: 80,
+        },
+        Bar {
+            id: Some(1),
+            ..Default::default()
+        }
+        .try_into()
+        .unwrap()
+    );
+
+    let err = Foo::try_from(Bar::default()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) id of the super struct Bar not initialized"
+    );
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true, default_missing = true)]
+struct FooDefaultMissing {
+    id: u32,
+    tags: Vec<String>,
+    #[fromsuper(default = "\"unnamed\".to_string()")]
+    name: String,
+}
+
+#[test]
+fn test_default_missing() {
+    assert_eq!(
+        FooDefaultMissing {
+            id: 0,
+            tags: vec![],
+            name: "unnamed".to_string(),
+        },
+        Bar::default().try_into().unwrap()
+    );
+    assert_eq!(
+        FooDefaultMissing {
+            id: 2,
+            tags: vec!["a".to_string()],
+            name: "x".to_string(),
+        },
+        Bar {
+            id: Some(2),
+            tags: Some(vec!["a".to_string()]),
+            name: Some("x".to_string()),
+            port: None,
+        }
+        .try_into()
+        .unwrap()
+    );
+}
+
+struct BarInner {
+    level: Option<u8>,
+}
+
+struct BarOuter {
+    inner: Option<BarInner>,
+    label: Option<String>,
+    count: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Default, FromSuper)]
+#[fromsuper(from_type = "BarInner", unpack = true)]
+struct FooInner {
+    level: u8,
+}
+
+fn positive(count: &u32) -> Result<(), &'static str> {
+    if *count > 0 {
+        Ok(())
+    } else {
+        Err("count is zero")
+    }
+}
+
+// the default values are of the field types, not of the source types
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "BarOuter", unpack = true)]
+struct FooOuter {
+    #[fromsuper(nested, default)]
+    inner: FooInner,
+    #[fromsuper(validate = "positive", default)]
+    count: u32,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a BarOuter", unpack = true, make_refs = true)]
+struct FooOuterRef<'a> {
+    #[fromsuper(default)]
+    label: &'a str,
+}
+
+#[test]
+fn test_default_of_converted_value() {
+    let bar = BarOuter {
+        inner: None,
+        label: None,
+        count: None,
+    };
+    let foo: FooOuterRef = (&bar).try_into().unwrap();
+    assert_eq!(FooOuterRef { label: "" }, foo);
+
+    // default values are neither converted nor validated
+    assert_eq!(
+        FooOuter {
+            inner: FooInner::default(),
+            count: 0,
+        },
+        bar.try_into().unwrap()
+    );
+
+    let err = FooOuter::try_from(BarOuter {
+        inner: Some(BarInner { level: None }),
+        label: None,
+        count: Some(0),
+    })
+    .unwrap_err();
+    assert_eq!(
+        vec![FooOuterField::Inner, FooOuterField::Count],
+        err.invalid_fields().collect::<Vec<_>>()
+    );
+}
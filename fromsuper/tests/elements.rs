@@ -0,0 +1,179 @@
+use std::collections::{BTreeMap, HashMap};
+
+use fromsuper::{ConversionError, ElementErrors, FromSuper, PathSegment};
+
+#[derive(Debug)]
+struct BarItem {
+    id: Option<u32>,
+    name: String,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "BarItem", unpack = true)]
+struct FooItem {
+    id: u32,
+    #[fromsuper(unpack = false)]
+    name: String,
+}
+
+struct Bar {
+    items: Vec<BarItem>,
+    by_name: HashMap<String, BarItem>,
+    sorted: Option<BTreeMap<u8, BarItem>>,
+    numbers: Vec<u64>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar")]
+struct Foo {
+    #[fromsuper(nested_elements)]
+    items: Vec<FooItem>,
+    #[fromsuper(nested_elements)]
+    by_name: HashMap<String, FooItem>,
+    #[fromsuper(nested_elements)]
+    sorted: Option<BTreeMap<u8, FooItem>>,
+    #[fromsuper(nested_elements)]
+    numbers: Vec<u8>,
+}
+
+fn item(id: Option<u32>) -> BarItem {
+    BarItem {
+        id,
+        name: "item".to_string(),
+    }
+}
+
+fn foo_item(id: u32) -> FooItem {
+    FooItem {
+        id,
+        name: "item".to_string(),
+    }
+}
+
+#[test]
+fn test_nested_elements() {
+    let foo = Foo::try_from(Bar {
+        items: vec![item(Some(1)), item(Some(2))],
+        by_name: HashMap::from([("a".to_string(), item(Some(3)))]),
+        sorted: Some(BTreeMap::from([(4, item(Some(4)))])),
+        numbers: vec![5],
+    })
+    .unwrap();
+    assert_eq!(
+        Foo {
+            items: vec![foo_item(1), foo_item(2)],
+            by_name: HashMap::from([("a".to_string(), foo_item(3))]),
+            sorted: Some(BTreeMap::from([(4, foo_item(4))])),
+            numbers: vec![5],
+        },
+        foo
+    );
+}
+
+#[test]
+fn test_nested_elements_errors() {
+    let err = Foo::try_from(Bar {
+        items: vec![item(Some(1)), item(None), item(None)],
+        by_name: HashMap::from([("a".to_string(), item(None))]),
+        sorted: None,
+        numbers: vec![1, 1000],
+    })
+    .unwrap_err();
+
+    assert_eq!(
+        vec![FooField::Items, FooField::ByName, FooField::Numbers],
+        err.invalid_fields().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec!["items[1].id", "items[2].id", "by_name[\"a\"].id"],
+        err.missing_paths()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) items[1].id, items[2].id, by_name[\"a\"].id of the super struct Bar not initialized; \
+         Attribute numbers[1] of the super struct Bar could not be converted: \
+         out of range integral type conversion attempted"
+    );
+
+    let source = std::error::Error::source(&err).unwrap();
+    let elements = source.downcast_ref::<ElementErrors>().unwrap();
+    assert_eq!(
+        vec![&PathSegment::Index(1), &PathSegment::Index(2)],
+        elements.invalid_elements().collect::<Vec<_>>()
+    );
+}
+
+struct BarSparse {
+    column: Vec<Option<u32>>,
+    fixed: [Option<char>; 3],
+    by_key: HashMap<&'static str, Option<u8>>,
+    items: Option<Vec<Option<BarItem>>>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "BarSparse", unpack = true)]
+struct FooSparse {
+    #[fromsuper(unpack_elements, unpack = false)]
+    column: Vec<u32>,
+    #[fromsuper(unpack_elements, unpack = false)]
+    fixed: [char; 3],
+    #[fromsuper(unpack_elements, unpack = false)]
+    by_key: HashMap<&'static str, u8>,
+    #[fromsuper(unpack_elements, nested_elements)]
+    items: Vec<FooItem>,
+}
+
+#[test]
+fn test_unpack_elements() {
+    let foo = FooSparse::try_from(BarSparse {
+        column: vec![Some(1), Some(2)],
+        fixed: [Some('a'), Some('b'), Some('c')],
+        by_key: HashMap::from([("x", Some(3))]),
+        items: Some(vec![Some(item(Some(4)))]),
+    })
+    .unwrap();
+    assert_eq!(
+        FooSparse {
+            column: vec![1, 2],
+            fixed: ['a', 'b', 'c'],
+            by_key: HashMap::from([("x", 3)]),
+            items: vec![foo_item(4)],
+        },
+        foo
+    );
+
+    let err = FooSparse::try_from(BarSparse {
+        column: vec![Some(1), None, Some(3), None],
+        fixed: [Some('a'), None, Some('c')],
+        by_key: HashMap::from([("x", None)]),
+        items: Some(vec![None, Some(item(None))]),
+    })
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) column[1], column[3], fixed[1], by_key[\"x\"], items[0], items[1].id \
+         of the super struct BarSparse not initialized"
+    );
+
+    let source = std::error::Error::source(&err).unwrap();
+    let elements = source.downcast_ref::<ElementErrors>().unwrap();
+    assert_eq!(
+        vec![&PathSegment::Index(1), &PathSegment::Index(3)],
+        elements.missing_elements().collect::<Vec<_>>()
+    );
+
+    let err = FooSparse::try_from(BarSparse {
+        column: vec![],
+        fixed: [Some('a'), Some('b'), Some('c')],
+        by_key: HashMap::new(),
+        items: None,
+    })
+    .unwrap_err();
+    assert_eq!(
+        vec![FooSparseField::Items],
+        err.missing_fields().collect::<Vec<_>>()
+    );
+}
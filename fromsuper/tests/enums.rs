@@ -0,0 +1,170 @@
+use fromsuper::FromSuper;
+
+#[derive(Debug, Clone)]
+enum Bar {
+    A(u32, Option<String>),
+    B { x: Option<u8>, y: char },
+    C,
+    D(Vec<u16>),
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true, unmodeled_variants = "D")]
+enum Foo {
+    A(#[fromsuper(unpack = false)] u32, String),
+    #[fromsuper(rename_from = "B")]
+    Renamed {
+        x: u8,
+        #[fromsuper(unpack = false)]
+        y: char,
+    },
+    C,
+}
+
+#[test]
+fn test_enum_variants() {
+    assert_eq!(
+        Foo::A(42, "hello".to_string()),
+        Bar::A(42, Some("hello".to_string())).try_into().unwrap()
+    );
+    assert_eq!(
+        Foo::Renamed { x: 1, y: 'y' },
+        Bar::B { x: Some(1), y: 'y' }.try_into().unwrap()
+    );
+    assert_eq!(Foo::C, Bar::C.try_into().unwrap());
+}
+
+#[test]
+fn test_enum_errors() {
+    let err = Foo::try_from(Bar::A(42, None)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) 1 of variant A of the super enum Bar not initialized"
+    );
+
+    let err = Foo::try_from(Bar::D(vec![])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Variant D of the super enum Bar is not modeled"
+    );
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", unmodeled_variants = "A, B, D")]
+enum FooNamed {
+    C,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar")]
+enum FooUnlisted {
+    C,
+}
+
+#[test]
+fn test_enum_unmodeled_named() {
+    assert_eq!(FooNamed::C, Bar::C.try_into().unwrap());
+
+    let err = FooNamed::try_from(Bar::D(vec![])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Variant D of the super enum Bar is not modeled"
+    );
+
+    // without listing the unmodeled variants, the encountered one cannot be named
+    let err = FooUnlisted::try_from(Bar::D(vec![])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Encountered a variant of the super enum Bar that is not modeled"
+    );
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar")]
+enum FooOther {
+    D(Vec<u16>),
+    #[fromsuper(other)]
+    Other,
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "&'a Bar", make_refs = true)]
+enum FooOtherRef<'a> {
+    D(&'a Vec<u16>),
+    #[fromsuper(other)]
+    Other(&'a Bar),
+}
+
+#[test]
+fn test_enum_other() {
+    // no TryFrom needed, as every variant is handled
+    let foo: FooOther = Bar::D(vec![1, 2]).into();
+    assert_eq!(FooOther::D(vec![1, 2]), foo);
+    assert_eq!(FooOther::Other, Bar::C.into());
+
+    let bar = Bar::D(vec![3]);
+    let foo: FooOtherRef = (&bar).into();
+    assert!(matches!(foo, FooOtherRef::D(d) if d == &vec![3]));
+
+    let bar = Bar::B { x: None, y: 'z' };
+    let foo: FooOtherRef = (&bar).into();
+    assert!(matches!(foo, FooOtherRef::Other(Bar::B { y: 'z', .. })));
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a Bar", unpack = true, make_refs = true)]
+enum FooRef<'a> {
+    A(#[fromsuper(unpack = false)] &'a u32, &'a String),
+    B { x: &'a u8 },
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a Bar", unpack = true)]
+enum FooCopied {
+    B {
+        x: u8,
+        #[fromsuper(unpack = false)]
+        y: char,
+    },
+}
+
+#[test]
+fn test_enum_ref() {
+    let bar = Bar::A(42, Some("hello".to_string()));
+    let foo: FooRef = (&bar).try_into().unwrap();
+    assert_eq!(FooRef::A(&42, &"hello".to_string()), foo);
+
+    let bar = Bar::B { x: Some(1), y: 'y' };
+    let foo: FooRef = (&bar).try_into().unwrap();
+    assert_eq!(FooRef::B { x: &1 }, foo);
+
+    let foo: FooCopied = (&bar).try_into().unwrap();
+    assert_eq!(FooCopied::B { x: 1, y: 'y' }, foo);
+}
+
+enum BarV2 {
+    C,
+    E,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(
+    from_type = "Bar",
+    from_type = "BarV2",
+    unmodeled_variants(Bar = "A, B, D", BarV2 = "E")
+)]
+enum FooMultiple {
+    C,
+}
+
+#[test]
+fn test_enum_multiple() {
+    assert_eq!(FooMultiple::C, Bar::C.try_into().unwrap());
+    assert_eq!(FooMultiple::C, BarV2::C.try_into().unwrap());
+
+    let err = FooMultiple::try_from(BarV2::E).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Variant E of the super enum BarV2 is not modeled"
+    );
+}
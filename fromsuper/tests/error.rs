@@ -0,0 +1,153 @@
+use fromsuper::FromSuper;
+
+use std::collections::HashSet;
+
+mod api {
+    use fromsuper::FromSuper;
+
+    #[derive(Clone)]
+    pub struct Bar {
+        pub a: Option<u32>,
+        pub b: Option<String>,
+    }
+
+    #[derive(Debug, PartialEq, FromSuper)]
+    #[fromsuper(
+        from_type = "Bar",
+        unpack = true,
+        error = "FooError",
+        error_vis = "pub",
+        error_derive = "Clone, Eq, Hash, PartialEq",
+        error_attr(non_exhaustive)
+    )]
+    pub struct Foo {
+        pub a: u32,
+        pub b: String,
+    }
+
+    pub fn convert(bar: Bar) -> Result<Foo, FooError> {
+        bar.try_into()
+    }
+}
+
+#[test]
+fn test_named_error() {
+    let bar = api::Bar {
+        a: Some(1),
+        b: None,
+    };
+
+    let err: api::FooError = api::convert(bar.clone()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) b of the super struct Bar not initialized"
+    );
+
+    let mut errors = HashSet::new();
+    errors.insert(err.clone());
+    assert!(errors.contains(&api::convert(bar).unwrap_err()));
+}
+
+mod summary {
+    use fromsuper::FromSuper;
+
+    pub struct Bar {
+        pub a: Option<u32>,
+    }
+
+    // the sub struct stays private, while its error is public
+    #[derive(FromSuper)]
+    #[fromsuper(
+        from_type = "Bar",
+        unpack = true,
+        error = "SummaryError",
+        error_vis = "pub",
+        field_enum = "SummaryPart"
+    )]
+    struct Summary {
+        #[allow(dead_code)]
+        a: u32,
+    }
+
+    pub fn check(bar: Bar) -> Result<(), SummaryError> {
+        Summary::try_from(bar).map(|_| ())
+    }
+}
+
+#[test]
+fn test_field_enum_visibility() {
+    let err = summary::check(summary::Bar { a: None }).unwrap_err();
+    assert_eq!(
+        vec![summary::SummaryPart::A],
+        err.missing_fields().collect::<Vec<_>>()
+    );
+}
+
+struct BarV1 {
+    a: Option<u32>,
+}
+
+struct BarV2 {
+    a: Option<u32>,
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(
+    from_type = "BarV1",
+    from_type = "BarV2",
+    unpack = true,
+    error(BarV1 = "FooV1Error", BarV2 = "FooV2Error")
+)]
+struct FooMultiple {
+    #[allow(dead_code)]
+    a: u32,
+}
+
+#[test]
+fn test_named_error_multiple() {
+    let err: FooV1Error = FooMultiple::try_from(BarV1 { a: None }).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) a of the super struct BarV1 not initialized"
+    );
+
+    let err: FooV2Error = FooMultiple::try_from(BarV2 { a: None }).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) a of the super struct BarV2 not initialized"
+    );
+}
+
+/// The generated error type and field enum are documented, so they can be part of a public API.
+pub mod documented {
+    #![deny(missing_docs)]
+
+    use fromsuper::FromSuper;
+
+    /// The super struct.
+    pub struct Bar {
+        /// The first field.
+        pub a: Option<u32>,
+    }
+
+    /// The sub struct.
+    #[derive(FromSuper)]
+    #[fromsuper(
+        from_type = "Bar",
+        unpack = true,
+        error = "FooError",
+        error_vis = "pub"
+    )]
+    pub struct Foo {
+        /// The first field.
+        pub a: u32,
+    }
+}
+
+#[test]
+fn test_documented_error() {
+    let err = documented::Foo::try_from(documented::Bar { a: None })
+        .err()
+        .unwrap();
+    assert!(err.is_missing(documented::FooField::A));
+}
@@ -0,0 +1,91 @@
+use fromsuper::FromSuper;
+
+struct Bar<T> {
+    id: Option<u32>,
+    the_name: Option<String>,
+    value: Option<T>,
+    port: String,
+}
+
+fn parse_port(port: String) -> Result<u16, std::num::ParseIntError> {
+    port.parse()
+}
+
+#[allow(dead_code)]
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "Bar<#T>", unpack = true)]
+struct Foo<T> {
+    id: u32,
+    #[fromsuper(rename_from = "the_name")]
+    name: String,
+    value: T,
+    #[fromsuper(try_with = "parse_port", unpack = false)]
+    port: u16,
+}
+
+#[test]
+fn test_missing_fields() {
+    let bar = Bar::<u8> {
+        id: Some(1),
+        the_name: None,
+        value: None,
+        port: "x".to_string(),
+    };
+
+    let err = Foo::try_from(bar).unwrap_err();
+    assert_eq!(
+        vec![FooField::Name, FooField::Value],
+        err.missing_fields().collect::<Vec<_>>()
+    );
+    assert!(err.is_missing(FooField::Name));
+    assert!(!err.is_missing(FooField::Id));
+    assert_eq!(
+        vec!["the_name", "value"],
+        err.missing_sources().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![FooField::Port],
+        err.invalid_fields().collect::<Vec<_>>()
+    );
+    assert_eq!("fields::Bar<u8>", err.super_type_name());
+
+    assert_eq!("name", FooField::Name.name());
+    assert_eq!("value", FooField::Value.to_string());
+}
+
+enum BarEnum {
+    A(Option<u32>),
+    B { some_field: Option<char> },
+}
+
+#[allow(dead_code)]
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "BarEnum", unpack = true)]
+enum FooEnum {
+    A(u32),
+    B { some_field: char },
+}
+
+#[allow(dead_code)]
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "(u8, Option<u16>)", unpack = true)]
+struct FooTuple(#[fromsuper(unpack = false)] u8, u16);
+
+#[test]
+fn test_field_enum_variants() {
+    let err = FooEnum::try_from(BarEnum::A(None)).unwrap_err();
+    assert!(err.is_missing(FooEnumField::A0));
+
+    let err = FooEnum::try_from(BarEnum::B { some_field: None }).unwrap_err();
+    assert_eq!(
+        vec![FooEnumField::BSomeField],
+        err.missing_fields().collect::<Vec<_>>()
+    );
+
+    let err = FooTuple::try_from((1, None)).unwrap_err();
+    assert_eq!(
+        vec![FooTupleField::Field1],
+        err.missing_fields().collect::<Vec<_>>()
+    );
+    assert_eq!("1", FooTupleField::Field1.name());
+}
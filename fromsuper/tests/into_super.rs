@@ -0,0 +1,131 @@
+use fromsuper::FromSuper;
+
+#[derive(Debug, PartialEq, Default)]
+struct Bar {
+    a: Option<u32>,
+    b: Option<String>,
+    c: Vec<u8>,
+    d: bool,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true, into_super)]
+struct Foo {
+    a: u32,
+    #[fromsuper(rename_from = "b")]
+    name: String,
+    #[fromsuper(unpack = false)]
+    c: Vec<u8>,
+}
+
+fn base() -> Bar {
+    Bar {
+        d: true,
+        ..Default::default()
+    }
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true, into_super = "base()")]
+struct FooWithBase {
+    a: u32,
+}
+
+#[test]
+fn test_into_super() {
+    let foo = Foo {
+        a: 1,
+        name: "name".to_string(),
+        c: vec![2],
+    };
+    assert_eq!(
+        Bar {
+            a: Some(1),
+            b: Some("name".to_string()),
+            c: vec![2],
+            d: false,
+        },
+        Bar::from(foo)
+    );
+
+    let bar: Bar = FooWithBase { a: 3 }.into();
+    assert_eq!(
+        Bar {
+            a: Some(3),
+            b: None,
+            c: vec![],
+            d: true,
+        },
+        bar
+    );
+
+    // converting back and forth keeps the modeled fields
+    let foo = Foo::try_from(Bar::from(Foo {
+        a: 4,
+        name: "x".to_string(),
+        c: vec![],
+    }))
+    .unwrap();
+    assert_eq!(4, foo.a);
+}
+
+#[derive(Debug, PartialEq, Default)]
+struct Outer<T> {
+    inner: Option<Bar>,
+    value: T,
+    other: u8,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Outer<#T>", unpack = true, into_super)]
+struct OuterSub<T> {
+    #[fromsuper(nested)]
+    inner: FooWithBase,
+    #[fromsuper(unpack = false)]
+    value: T,
+}
+
+#[test]
+fn test_into_super_nested() {
+    let sub = OuterSub {
+        inner: FooWithBase { a: 5 },
+        value: 'x',
+    };
+    assert_eq!(
+        Outer {
+            inner: Some(Bar {
+                a: Some(5),
+                d: true,
+                ..Default::default()
+            }),
+            value: 'x',
+            other: 0,
+        },
+        Outer::from(sub)
+    );
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(
+    from_type = "Bar",
+    from_type = "&'a Bar",
+    unpack = true,
+    clone = true,
+    into_super
+)]
+struct FooBorrowed {
+    a: u32,
+    #[fromsuper(unpack = false)]
+    c: Vec<u8>,
+}
+
+#[test]
+fn test_into_super_borrowed() {
+    let bar = Bar {
+        a: Some(6),
+        c: vec![1],
+        ..Default::default()
+    };
+    let foo = FooBorrowed::try_from(&bar).unwrap();
+    assert_eq!(bar, Bar::from(foo));
+}
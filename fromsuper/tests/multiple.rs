@@ -0,0 +1,102 @@
+use fromsuper::FromSuper;
+
+struct BarV1 {
+    id: Option<u32>,
+    name: String,
+}
+
+struct BarV2 {
+    identifier: Option<u32>,
+    name: Option<String>,
+    #[allow(dead_code)]
+    extra: Vec<u8>,
+}
+
+#[derive(PartialEq, Debug, FromSuper)]
+#[fromsuper(from_type = "BarV1", from_type = "BarV2", unpack = true)]
+struct Foo {
+    #[fromsuper(rename_from(BarV2 = "identifier"))]
+    id: u32,
+    #[fromsuper(unpack(BarV1 = false))]
+    name: String,
+}
+
+#[test]
+fn test_multiple_from_types() {
+    let foo: Foo = BarV1 {
+        id: Some(42),
+        name: "v1".to_string(),
+    }
+    .try_into()
+    .unwrap();
+    assert_eq!(
+        foo,
+        Foo {
+            id: 42,
+            name: "v1".to_string()
+        }
+    );
+
+    let foo: Foo = BarV2 {
+        identifier: Some(53),
+        name: Some("v2".to_string()),
+        extra: vec![],
+    }
+    .try_into()
+    .unwrap();
+    assert_eq!(
+        foo,
+        Foo {
+            id: 53,
+            name: "v2".to_string()
+        }
+    );
+
+    assert!(Foo::try_from(BarV2 {
+        identifier: Some(53),
+        name: None,
+        extra: vec![],
+    })
+    .is_err());
+}
+
+mod v1 {
+    pub struct Bar {
+        pub x: u32,
+    }
+}
+
+mod v2 {
+    pub struct Bar {
+        #[allow(dead_code)]
+        pub x: u32,
+        pub y: u32,
+    }
+}
+
+#[derive(PartialEq, Debug, FromSuper)]
+#[fromsuper(from_type = "v1::Bar", from_type = "v2::Bar")]
+struct FooQualified {
+    #[fromsuper(rename_from(v1::Bar = "x", v2::Bar = "y"))]
+    z: u32,
+}
+
+#[test]
+fn test_multiple_from_types_qualified() {
+    assert_eq!(FooQualified { z: 1 }, v1::Bar { x: 1 }.into());
+    assert_eq!(FooQualified { z: 2 }, v2::Bar { x: 1, y: 2 }.into());
+}
+
+#[derive(PartialEq, Debug, FromSuper)]
+#[fromsuper(from_type = "v1::Bar", from_type = "v2::Bar")]
+struct FooOverlapping {
+    // the more specific key takes precedence for `v2::Bar`
+    #[fromsuper(rename_from(Bar = "x", v2::Bar = "y"))]
+    z: u32,
+}
+
+#[test]
+fn test_overlapping_keys() {
+    assert_eq!(FooOverlapping { z: 1 }, v1::Bar { x: 1 }.into());
+    assert_eq!(FooOverlapping { z: 2 }, v2::Bar { x: 1, y: 2 }.into());
+}
@@ -0,0 +1,189 @@
+use fromsuper::{ConversionError, FieldPath, FromSuper, PathSegment};
+
+#[derive(Debug, Clone)]
+struct BarInner {
+    a: Option<u32>,
+    b: char,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "BarInner", unpack = true)]
+struct FooInner {
+    a: u32,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "BarInner")]
+struct FooInnerInfallible {
+    b: char,
+}
+
+#[derive(Debug, Clone)]
+struct Bar {
+    inner: BarInner,
+    maybe_inner: Option<BarInner>,
+    id: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true)]
+struct Foo {
+    #[fromsuper(nested, unpack = false)]
+    inner: FooInner,
+    #[fromsuper(nested, rename_from = "maybe_inner")]
+    other: FooInnerInfallible,
+    id: u64,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar")]
+struct FooOnlyNested {
+    #[fromsuper(nested)]
+    inner: FooInner,
+}
+
+#[test]
+fn test_nested() {
+    let bar = Bar {
+        inner: BarInner { a: Some(1), b: 'x' },
+        maybe_inner: Some(BarInner { a: None, b: 'y' }),
+        id: Some(2),
+    };
+
+    assert_eq!(
+        Foo {
+            inner: FooInner { a: 1 },
+            other: FooInnerInfallible { b: 'y' },
+            id: 2,
+        },
+        bar.clone().try_into().unwrap()
+    );
+    assert_eq!(
+        FooOnlyNested {
+            inner: FooInner { a: 1 },
+        },
+        bar.try_into().unwrap()
+    );
+}
+
+#[test]
+fn test_nested_errors() {
+    let bar = Bar {
+        inner: BarInner { a: None, b: 'x' },
+        maybe_inner: None,
+        id: None,
+    };
+
+    // all errors are reported, not only the first one
+    let err = Foo::try_from(bar).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) other, id, inner.a of the super struct Bar not initialized"
+    );
+    assert_eq!(
+        vec!["other", "id", "inner.a"],
+        err.missing_paths()
+            .iter()
+            .map(FieldPath::to_string)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        &[PathSegment::Field("inner"), PathSegment::Field("a")],
+        err.missing_paths()[2].segments()
+    );
+
+    let source = std::error::Error::source(&err).unwrap();
+    assert_eq!(
+        source.to_string(),
+        "Attribute(s) a of the super struct BarInner not initialized"
+    );
+}
+
+#[derive(Debug)]
+enum BarEnum {
+    A(BarInner),
+    B,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "BarEnum", unmodeled_variants = "B")]
+enum FooEnum {
+    A(#[fromsuper(nested)] FooInner),
+}
+
+#[test]
+fn test_nested_enum() {
+    let bar = BarEnum::A(BarInner { a: Some(3), b: 'z' });
+    assert_eq!(FooEnum::A(FooInner { a: 3 }), bar.try_into().unwrap());
+
+    let err = FooEnum::try_from(BarEnum::A(BarInner { a: None, b: 'z' })).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) 0.a of variant A of the super enum BarEnum not initialized"
+    );
+    assert!(FooEnum::try_from(BarEnum::B).is_err());
+}
+
+fn parse_port(port: String) -> Result<u16, std::num::ParseIntError> {
+    port.parse()
+}
+
+struct BarHeader {
+    id: Option<u32>,
+    port: String,
+    kind: BarEnum,
+}
+
+struct BarOuter {
+    header: BarHeader,
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "BarHeader", unpack = true, shared_error = true)]
+struct FooHeader {
+    #[allow(dead_code)]
+    id: u32,
+    #[allow(dead_code)]
+    #[fromsuper(unpack = false, try_with = "parse_port")]
+    port: u16,
+    #[allow(dead_code)]
+    #[fromsuper(unpack = false, nested)]
+    kind: FooEnum,
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "BarOuter")]
+struct FooOuter {
+    #[allow(dead_code)]
+    #[fromsuper(nested)]
+    header: FooHeader,
+}
+
+#[test]
+fn test_nested_error_paths() {
+    let bar = BarOuter {
+        header: BarHeader {
+            id: None,
+            port: "http".to_string(),
+            kind: BarEnum::B,
+        },
+    };
+
+    let err = FooOuter::try_from(bar).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) header.id of the super struct BarOuter not initialized; \
+         Attribute header.port of the super struct BarOuter could not be converted: \
+         invalid digit found in string; \
+         Attribute header.kind of the super struct BarOuter could not be converted: \
+         Variant B of the super enum BarEnum is not modeled"
+    );
+
+    // the source chains to the inner conversion's error
+    let source = std::error::Error::source(&err).unwrap();
+    assert!(source
+        .to_string()
+        .starts_with("Attribute(s) id of the super struct"));
+    let source = source.source().unwrap();
+    assert_eq!(source.to_string(), "invalid digit found in string");
+}
@@ -0,0 +1,222 @@
+use fromsuper::FromSuper;
+
+struct Bar {
+    id: Option<u32>,
+    name: Option<String>,
+    count: u64,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a Bar", unpack = true)]
+struct Foo<'a> {
+    id: u32,
+    #[fromsuper(make_ref)]
+    name: &'a String,
+    #[fromsuper(unpack = false)]
+    count: u64,
+    #[fromsuper(make_ref, unpack = false)]
+    data: &'a Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a Bar", unpack = true, make_refs = true)]
+struct FooMostlyRefs<'a> {
+    #[fromsuper(make_ref = false)]
+    id: u32,
+    name: &'a String,
+    #[fromsuper(unpack = false)]
+    data: &'a Vec<u8>,
+}
+
+#[test]
+fn test_make_ref_per_field() {
+    let bar = Bar {
+        id: Some(1),
+        name: Some("name".to_string()),
+        count: 2,
+        data: vec![3],
+    };
+
+    let foo: Foo = (&bar).try_into().unwrap();
+    assert_eq!(
+        Foo {
+            id: 1,
+            name: &"name".to_string(),
+            count: 2,
+            data: &vec![3],
+        },
+        foo
+    );
+
+    let foo: FooMostlyRefs = (&bar).try_into().unwrap();
+    assert_eq!(
+        FooMostlyRefs {
+            id: 1,
+            name: &"name".to_string(),
+            data: &vec![3],
+        },
+        foo
+    );
+}
+
+enum BarEnum {
+    A { id: u32, name: String },
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a BarEnum")]
+enum FooEnum<'a> {
+    A {
+        id: u32,
+        #[fromsuper(make_ref)]
+        name: &'a String,
+    },
+}
+
+#[test]
+fn test_make_ref_enum() {
+    let bar = BarEnum::A {
+        id: 1,
+        name: "a".to_string(),
+    };
+    let foo: FooEnum = (&bar).try_into().unwrap();
+    assert_eq!(
+        FooEnum::A {
+            id: 1,
+            name: &"a".to_string()
+        },
+        foo
+    );
+}
+
+struct Config {
+    name: String,
+    port: Option<u16>,
+    verbose: bool,
+}
+
+#[derive(FromSuper)]
+#[fromsuper(from_type = "&'a mut Config", unpack = true, make_refs = "mut")]
+struct ConfigEdit<'a> {
+    #[fromsuper(unpack = false)]
+    name: &'a mut String,
+    port: &'a mut u16,
+    #[fromsuper(unpack = false, make_ref = false)]
+    verbose: bool,
+}
+
+#[test]
+fn test_make_refs_mut() {
+    let mut config = Config {
+        name: "server".to_string(),
+        port: Some(80),
+        verbose: true,
+    };
+
+    let edit: ConfigEdit = (&mut config).try_into().unwrap();
+    edit.name.push_str("-1");
+    *edit.port += 8000;
+    assert!(edit.verbose);
+
+    assert_eq!("server-1", config.name);
+    assert_eq!(Some(8080), config.port);
+
+    config.port = None;
+    assert!(ConfigEdit::try_from(&mut config).is_err());
+}
+
+enum ConfigEnum {
+    Server { name: String, port: Option<u16> },
+    Client,
+}
+
+#[derive(FromSuper)]
+#[fromsuper(from_type = "&'a mut ConfigEnum", unpack = true, make_refs = "mut")]
+enum ConfigEnumEdit<'a> {
+    Server {
+        #[fromsuper(unpack = false)]
+        name: &'a mut String,
+        port: &'a mut u16,
+    },
+}
+
+#[test]
+fn test_make_refs_mut_enum() {
+    let mut config = ConfigEnum::Server {
+        name: "server".to_string(),
+        port: Some(80),
+    };
+
+    match ConfigEnumEdit::try_from(&mut config).unwrap() {
+        ConfigEnumEdit::Server { name, port } => {
+            name.push_str("-1");
+            *port = 8080;
+        }
+    }
+    assert!(matches!(
+        config,
+        ConfigEnum::Server { ref name, port: Some(8080) } if name == "server-1"
+    ));
+
+    assert!(ConfigEnumEdit::try_from(&mut ConfigEnum::Client).is_err());
+}
+
+struct Document {
+    title: Option<String>,
+    subtitle: Option<String>,
+    tags: Vec<String>,
+    size: Box<u64>,
+    pages: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a Document", unpack = true, make_refs = true)]
+struct DocumentRef<'a> {
+    title: &'a str,
+    #[fromsuper(unpack = false)]
+    subtitle: Option<&'a str>,
+    #[fromsuper(unpack = false)]
+    tags: &'a [String],
+    #[fromsuper(unpack = false)]
+    size: &'a u64,
+    #[fromsuper(unpack = false)]
+    pages: Option<&'a u32>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a mut Document", make_refs = "mut")]
+struct DocumentMut<'a> {
+    subtitle: Option<&'a mut str>,
+    tags: &'a mut [String],
+}
+
+#[test]
+fn test_make_refs_deref() {
+    let mut doc = Document {
+        title: Some("Title".to_string()),
+        subtitle: None,
+        tags: vec!["a".to_string()],
+        size: Box::new(100),
+        pages: Some(3),
+    };
+
+    let doc_ref: DocumentRef = (&doc).try_into().unwrap();
+    assert_eq!(
+        DocumentRef {
+            title: "Title",
+            subtitle: None,
+            tags: &["a".to_string()],
+            size: &100,
+            pages: Some(&3),
+        },
+        doc_ref
+    );
+
+    doc.subtitle = Some("sub".to_string());
+    let doc_mut: DocumentMut = (&mut doc).into();
+    doc_mut.subtitle.unwrap().make_ascii_uppercase();
+    doc_mut.tags[0].push('b');
+    assert_eq!(Some("SUB"), doc.subtitle.as_deref());
+    assert_eq!(vec!["ab".to_string()], doc.tags);
+}
@@ -0,0 +1,161 @@
+use fromsuper::{ConversionError, FromSuper};
+
+#[derive(Debug, Clone, PartialEq)]
+struct ParseError(&'static str);
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "could not parse {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+struct Bar {
+    id: Result<u32, ParseError>,
+    name: Option<String>,
+    port: Result<u16, ParseError>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true)]
+struct Foo {
+    #[fromsuper(unpack_result)]
+    id: u32,
+    name: String,
+    #[fromsuper(unpack_result)]
+    port: u16,
+}
+
+#[test]
+fn test_unpack_result() {
+    let foo: Foo = Bar {
+        id: Ok(1),
+        name: Some("name".to_string()),
+        port: Ok(80),
+    }
+    .try_into()
+    .unwrap();
+    assert_eq!(
+        Foo {
+            id: 1,
+            name: "name".to_string(),
+            port: 80,
+        },
+        foo
+    );
+
+    let err = Foo::try_from(Bar {
+        id: Err(ParseError("id")),
+        name: None,
+        port: Err(ParseError("port")),
+    })
+    .unwrap_err();
+    assert_eq!(
+        vec![FooField::Name],
+        err.missing_fields().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![FooField::Id, FooField::Port],
+        err.invalid_fields().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) name of the super struct Bar not initialized; \
+         Attribute id of the super struct Bar could not be converted: could not parse id; \
+         Attribute port of the super struct Bar could not be converted: could not parse port"
+    );
+    let (path, source) = &err.invalid_paths()[1];
+    assert_eq!("port", path.to_string());
+    assert_eq!(
+        Some(&ParseError("port")),
+        source.downcast_ref::<ParseError>()
+    );
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a Bar", make_refs = true)]
+struct FooRef<'a> {
+    #[fromsuper(unpack_result)]
+    id: &'a u32,
+    name: &'a Option<String>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", into_super = "base()")]
+struct FooBack {
+    #[fromsuper(unpack_result)]
+    port: u16,
+}
+
+fn base() -> Bar {
+    Bar {
+        id: Err(ParseError("id")),
+        name: None,
+        port: Err(ParseError("port")),
+    }
+}
+
+#[test]
+fn test_unpack_result_ref() {
+    let bar = Bar {
+        id: Ok(1),
+        name: None,
+        port: Err(ParseError("port")),
+    };
+    let foo: FooRef = (&bar).try_into().unwrap();
+    assert_eq!(
+        FooRef {
+            id: &1,
+            name: &None
+        },
+        foo
+    );
+
+    let bar = Bar::from(FooBack { port: 80 });
+    assert_eq!(Ok(80), bar.port);
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "&'a Bar", make_refs = true)]
+struct FooRefPort<'a> {
+    #[fromsuper(unpack_result)]
+    #[allow(dead_code)]
+    port: &'a u16,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a Bar", clone = true)]
+struct FooCloned {
+    #[fromsuper(unpack_result)]
+    id: u32,
+    #[fromsuper(unpack_result)]
+    port: u16,
+}
+
+#[test]
+fn test_unpack_result_borrowed_err() {
+    let bar = Bar {
+        id: Ok(1),
+        name: None,
+        port: Err(ParseError("port")),
+    };
+
+    // the errors of the borrowed results are cloned into the conversion error
+    let err = FooRefPort::try_from(&bar).unwrap_err();
+    assert_eq!(
+        vec![FooRefPortField::Port],
+        err.invalid_fields().collect::<Vec<_>>()
+    );
+    let err = FooCloned::try_from(&bar).unwrap_err();
+    assert_eq!(
+        vec![FooClonedField::Port],
+        err.invalid_fields().collect::<Vec<_>>()
+    );
+    let (_, source) = &err.invalid_paths()[0];
+    assert_eq!(
+        Some(&ParseError("port")),
+        source.downcast_ref::<ParseError>()
+    );
+    assert!(bar.port.is_err());
+}
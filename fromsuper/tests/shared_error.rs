@@ -0,0 +1,92 @@
+use fromsuper::{FromSuper, MissingFields};
+
+struct Bar {
+    a: Option<u32>,
+    b: Option<String>,
+    c: String,
+}
+
+fn parse(c: String) -> Result<u8, std::num::ParseIntError> {
+    c.parse()
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true, shared_error = true)]
+struct Foo {
+    #[allow(dead_code)]
+    a: u32,
+    #[allow(dead_code)]
+    #[fromsuper(rename_from = "b")]
+    name: String,
+    #[allow(dead_code)]
+    #[fromsuper(unpack = false, try_with = "parse")]
+    c: u8,
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true, shared_error = true)]
+struct FooOther {
+    #[allow(dead_code)]
+    a: u32,
+}
+
+fn missing<T: TryFrom<Bar, Error = MissingFields>>(bar: Bar) -> Vec<&'static str> {
+    match T::try_from(bar) {
+        Ok(_) => vec![],
+        Err(err) => err.missing_fields().collect(),
+    }
+}
+
+#[test]
+fn test_shared_error() {
+    let bar = || Bar {
+        a: None,
+        b: None,
+        c: "300".to_string(),
+    };
+
+    assert_eq!(vec!["a", "name"], missing::<Foo>(bar()));
+    assert_eq!(vec!["a"], missing::<FooOther>(bar()));
+
+    let err = Foo::try_from(bar()).unwrap_err();
+    assert_eq!("shared_error::Foo", err.sub_type_name());
+    assert_eq!("shared_error::Bar", err.super_type_name());
+    assert!(err.is_missing("name"));
+    assert_eq!(vec!["a", "b"], err.missing_sources().collect::<Vec<_>>());
+    assert_eq!(vec!["c"], err.invalid_fields().collect::<Vec<_>>());
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) a, name of the super struct shared_error::Bar not initialized; \
+         Attribute c of the super struct shared_error::Bar could not be converted: \
+         number too large to fit in target type"
+    );
+}
+
+enum BarEnum {
+    A(Option<u8>),
+    B,
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "BarEnum", unpack = true, shared_error = true)]
+enum FooEnum {
+    A(#[allow(dead_code)] u8),
+}
+
+#[test]
+fn test_shared_error_enum() {
+    let err = FooEnum::try_from(BarEnum::A(None)).unwrap_err();
+    assert_eq!(Some("A"), err.variant());
+    assert!(!err.is_unmodeled());
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) 0 of variant A of the super enum shared_error::BarEnum not initialized"
+    );
+
+    let err = FooEnum::try_from(BarEnum::B).unwrap_err();
+    assert!(err.is_unmodeled());
+    assert_eq!(
+        err.to_string(),
+        "Encountered a variant of the super enum shared_error::BarEnum that is not modeled"
+    );
+}
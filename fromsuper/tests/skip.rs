@@ -0,0 +1,89 @@
+use fromsuper::FromSuper;
+
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+struct Bar {
+    a: Option<u32>,
+    b: String,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true)]
+struct Foo {
+    a: u32,
+    #[fromsuper(skip)]
+    cache: HashMap<u32, String>,
+    #[fromsuper(skip, default = "1")]
+    counter: usize,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar")]
+struct FooMarker<T> {
+    b: String,
+    marker: PhantomData<T>,
+}
+
+#[test]
+fn test_skip() {
+    assert_eq!(
+        Foo {
+            a: 1,
+            cache: HashMap::new(),
+            counter: 1,
+        },
+        Bar {
+            a: Some(1),
+            b: "b".to_string(),
+        }
+        .try_into()
+        .unwrap()
+    );
+
+    let foo: FooMarker<char> = Bar {
+        a: None,
+        b: "b".to_string(),
+    }
+    .into();
+    assert_eq!(
+        FooMarker {
+            b: "b".to_string(),
+            marker: PhantomData
+        },
+        foo
+    );
+}
+
+struct BarTuple(#[allow(dead_code)] u8, u16);
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "BarTuple")]
+struct FooTuple(#[fromsuper(skip, default = "'x'")] char, u16);
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", from_type = "BarTuple")]
+struct FooMultiple {
+    #[fromsuper(skip(BarTuple = true), default(BarTuple = "\"tuple\".to_string()"))]
+    b: String,
+}
+
+#[test]
+fn test_skip_tuple() {
+    assert_eq!(FooTuple('x', 2), BarTuple(1, 2).into());
+
+    assert_eq!(
+        FooMultiple {
+            b: "tuple".to_string()
+        },
+        BarTuple(1, 2).into()
+    );
+    assert_eq!(
+        FooMultiple { b: "b".to_string() },
+        Bar {
+            a: None,
+            b: "b".to_string()
+        }
+        .into()
+    );
+}
@@ -0,0 +1,80 @@
+use fromsuper::FromSuper;
+
+#[derive(Debug, Clone)]
+struct BarTuple(u32, Option<String>, char);
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "BarTuple")]
+struct FooTuple(u32);
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "BarTuple", unpack = true)]
+struct FooTupleReordered(
+    #[fromsuper(rename_from = "2", unpack = false)] char,
+    #[fromsuper(rename_from = "1")] String,
+);
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "BarTuple", unpack = true)]
+struct FooNamedFromTuple {
+    #[fromsuper(rename_from = "0", unpack = false)]
+    id: u32,
+    #[fromsuper(rename_from = "1")]
+    name: String,
+}
+
+#[test]
+fn test_from_tuple() {
+    let bar = BarTuple(42, Some("hello".to_string()), 'x');
+
+    assert_eq!(FooTuple(42), bar.clone().into());
+    assert_eq!(
+        FooTupleReordered('x', "hello".to_string()),
+        bar.clone().try_into().unwrap()
+    );
+    assert_eq!(
+        FooNamedFromTuple {
+            id: 42,
+            name: "hello".to_string()
+        },
+        bar.try_into().unwrap()
+    );
+
+    let err = FooNamedFromTuple::try_from(BarTuple(42, None, 'x')).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) name of the super struct BarTuple not initialized"
+    );
+}
+
+struct BarNamed {
+    a: Option<u32>,
+    #[allow(dead_code)]
+    b: u64,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "BarNamed", unpack = true)]
+struct FooTupleFromNamed(#[fromsuper(rename_from = "a")] u32);
+
+#[test]
+fn test_tuple_from_named() {
+    assert_eq!(
+        FooTupleFromNamed(1),
+        BarNamed { a: Some(1), b: 2 }.try_into().unwrap()
+    );
+    assert!(FooTupleFromNamed::try_from(BarNamed { a: None, b: 2 }).is_err());
+}
+
+struct BarGeneric<T, U>(T, U);
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "&'a BarGeneric<#T, #U>", make_refs = true)]
+struct FooGenericRef<'a, U>(#[fromsuper(rename_from = "1")] &'a U);
+
+#[test]
+fn test_tuple_ref() {
+    let bar = BarGeneric(1u8, "two");
+    let foo: FooGenericRef<_> = (&bar).into();
+    assert_eq!(*foo.0, "two");
+}
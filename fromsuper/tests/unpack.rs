@@ -0,0 +1,206 @@
+use fromsuper::{FromSuper, Pack, Unpack};
+
+/// A value that is only present once it has been tracked
+struct Tracked<T> {
+    value: T,
+    tracked: bool,
+}
+
+impl<T> Unpack for Tracked<T> {
+    type Inner = T;
+
+    fn unpack(self) -> Option<T> {
+        self.tracked.then_some(self.value)
+    }
+
+    fn unpack_ref(&self) -> Option<&T> {
+        self.tracked.then_some(&self.value)
+    }
+
+    fn unpack_mut(&mut self) -> Option<&mut T> {
+        self.tracked.then_some(&mut self.value)
+    }
+}
+
+impl<T> Pack for Tracked<T> {
+    fn pack(inner: T) -> Self {
+        Tracked {
+            value: inner,
+            tracked: true,
+        }
+    }
+}
+
+struct Bar {
+    a: Tracked<u32>,
+    b: Box<Option<String>>,
+    c: Option<Box<u64>>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true)]
+struct Foo {
+    a: u32,
+    b: String,
+    c: Box<u64>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a Bar", unpack = true, make_refs = true)]
+struct FooRef<'a> {
+    a: &'a u32,
+    b: &'a str,
+    c: &'a u64,
+}
+
+#[derive(FromSuper)]
+#[fromsuper(from_type = "&'a mut Bar", unpack = true, make_refs = "mut")]
+struct FooMut<'a> {
+    a: &'a mut u32,
+}
+
+fn bar(tracked: bool) -> Bar {
+    Bar {
+        a: Tracked { value: 1, tracked },
+        b: Box::new(Some("b".to_string())),
+        c: Some(Box::new(3)),
+    }
+}
+
+#[test]
+fn test_unpack_trait() {
+    assert_eq!(
+        Foo {
+            a: 1,
+            b: "b".to_string(),
+            c: Box::new(3),
+        },
+        Foo::try_from(bar(true)).unwrap()
+    );
+
+    let err = Foo::try_from(bar(false)).unwrap_err();
+    assert_eq!(vec![FooField::A], err.missing_fields().collect::<Vec<_>>());
+
+    let bar_ref = bar(true);
+    assert_eq!(
+        FooRef {
+            a: &1,
+            b: "b",
+            c: &3
+        },
+        FooRef::try_from(&bar_ref).unwrap()
+    );
+    assert!(bar_ref.a.is_present());
+    assert!(!bar(false).a.is_present());
+
+    let mut bar_mut = bar(true);
+    *FooMut::try_from(&mut bar_mut).unwrap().a += 1;
+    assert_eq!(2, bar_mut.a.value);
+}
+
+#[derive(Debug, PartialEq)]
+struct BarDeep {
+    a: Option<Option<u32>>,
+    b: Option<Box<Option<Option<String>>>>,
+    c: Option<Option<u8>>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "BarDeep", unpack = true)]
+struct FooDeep {
+    #[fromsuper(unpack_depth = 2)]
+    a: u32,
+    #[fromsuper(unpack_depth = 2)]
+    b: Option<String>,
+    c: Option<u8>,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "&'a BarDeep", unpack = true, make_refs = true)]
+struct FooDeepRef<'a> {
+    #[fromsuper(unpack_depth = 2)]
+    a: &'a u32,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "BarDeep", unpack = true, into_super = "bar_deep()")]
+struct FooDeepBack {
+    #[fromsuper(unpack_depth = "2")]
+    a: u32,
+}
+
+fn bar_deep() -> BarDeep {
+    BarDeep {
+        a: None,
+        b: None,
+        c: Some(None),
+    }
+}
+
+#[test]
+fn test_unpack_depth() {
+    let foo = FooDeep::try_from(BarDeep {
+        a: Some(Some(1)),
+        b: Some(Box::new(Some(None))),
+        c: Some(None),
+    })
+    .unwrap();
+    assert_eq!(
+        FooDeep {
+            a: 1,
+            b: None,
+            c: None,
+        },
+        foo
+    );
+
+    for (a, b) in [(Some(None), None), (None, Some(Box::new(None)))] {
+        let err = FooDeep::try_from(BarDeep { a, b, c: None }).unwrap_err();
+        assert_eq!(
+            vec![FooDeepField::A, FooDeepField::B, FooDeepField::C],
+            err.missing_fields().collect::<Vec<_>>()
+        );
+    }
+
+    let bar = BarDeep {
+        a: Some(Some(2)),
+        ..bar_deep()
+    };
+    assert_eq!(FooDeepRef { a: &2 }, FooDeepRef::try_from(&bar).unwrap());
+
+    assert_eq!(
+        BarDeep {
+            a: Some(Some(3)),
+            b: None,
+            c: Some(None),
+        },
+        BarDeep::from(FooDeepBack { a: 3 })
+    );
+}
+
+#[derive(FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true, into_super = "bar(false)", apply_to)]
+struct FooBack {
+    a: u32,
+    b: String,
+}
+
+#[test]
+fn test_unpack_trait_back() {
+    let back = Bar::from(FooBack {
+        a: 4,
+        b: "x".to_string(),
+    });
+    assert!(back.a.tracked);
+    assert_eq!(4, back.a.value);
+    assert_eq!(Some("x".to_string()), *back.b);
+
+    let mut target = bar(false);
+    FooBack {
+        a: 5,
+        b: "y".to_string(),
+    }
+    .apply_to(&mut target);
+    assert_eq!(Some(&5), target.a.unpack_ref());
+    assert_eq!(Some("y"), target.b.as_deref());
+}
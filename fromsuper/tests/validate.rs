@@ -0,0 +1,201 @@
+use std::error::Error;
+
+use fromsuper::{ConversionError, FromSuper, MissingFields};
+
+#[derive(Debug, Clone, PartialEq)]
+struct OutOfRange(&'static str);
+
+impl std::fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is out of range", self.0)
+    }
+}
+
+impl std::error::Error for OutOfRange {}
+
+fn valid_port(port: &u16) -> Result<(), OutOfRange> {
+    if *port >= 1024 {
+        Ok(())
+    } else {
+        Err(OutOfRange("port"))
+    }
+}
+
+fn non_empty(name: &str) -> Result<(), &'static str> {
+    if name.is_empty() {
+        Err("name is empty")
+    } else {
+        Ok(())
+    }
+}
+
+fn valid_range(foo: &Foo) -> Result<(), OutOfRange> {
+    if foo.min <= foo.max {
+        Ok(())
+    } else {
+        Err(OutOfRange("min"))
+    }
+}
+
+struct Bar {
+    name: Option<String>,
+    port: Option<u16>,
+    min: u32,
+    max: u32,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true, validate = "valid_range")]
+struct Foo {
+    #[fromsuper(validate = "non_empty")]
+    name: String,
+    #[fromsuper(validate = "valid_port")]
+    port: u16,
+    #[fromsuper(unpack = false)]
+    min: u32,
+    #[fromsuper(unpack = false)]
+    max: u32,
+}
+
+#[test]
+fn test_validate() {
+    let foo = Foo::try_from(Bar {
+        name: Some("name".to_string()),
+        port: Some(8080),
+        min: 1,
+        max: 2,
+    })
+    .unwrap();
+    assert_eq!(
+        Foo {
+            name: "name".to_string(),
+            port: 8080,
+            min: 1,
+            max: 2,
+        },
+        foo
+    );
+
+    // failed validations are reported along with missing fields
+    let err = Foo::try_from(Bar {
+        name: None,
+        port: Some(80),
+        min: 1,
+        max: 2,
+    })
+    .unwrap_err();
+    assert_eq!(
+        vec![FooField::Name],
+        err.missing_fields().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![FooField::Port],
+        err.invalid_fields().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) name of the super struct Bar not initialized; \
+         Attribute port of the super struct Bar could not be converted: port is out of range"
+    );
+
+    let err = Foo::try_from(Bar {
+        name: Some(String::new()),
+        port: Some(8080),
+        min: 1,
+        max: 2,
+    })
+    .unwrap_err();
+    assert_eq!(
+        vec![FooField::Name],
+        err.invalid_fields().collect::<Vec<_>>()
+    );
+    assert_eq!("name is empty", err.source().unwrap().to_string());
+
+    // the struct is only validated once all fields are fine
+    let err = Foo::try_from(Bar {
+        name: Some("name".to_string()),
+        port: Some(8080),
+        min: 3,
+        max: 2,
+    })
+    .unwrap_err();
+    assert_eq!(0, err.missing_fields().count());
+    assert_eq!(0, err.invalid_fields().count());
+    assert_eq!(
+        Some(&OutOfRange("min")),
+        err.validation_error()
+            .and_then(|e| e.downcast_ref::<OutOfRange>())
+    );
+    assert_eq!(
+        err.to_string(),
+        "The converted Foo is invalid: min is out of range"
+    );
+}
+
+struct BarPlain {
+    port: u16,
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(from_type = "BarPlain")]
+struct FooPlain {
+    #[allow(dead_code)]
+    #[fromsuper(validate = "valid_port")]
+    port: u16,
+}
+
+#[test]
+fn test_validate_without_unpacking() {
+    assert!(FooPlain::try_from(BarPlain { port: 8080 }).is_ok());
+
+    let err = FooPlain::try_from(BarPlain { port: 22 }).unwrap_err();
+    assert_eq!(
+        vec![FooPlainField::Port],
+        err.invalid_fields().collect::<Vec<_>>()
+    );
+    assert_eq!("port", err.invalid_paths()[0].0.to_string());
+}
+
+fn valid_shared(foo: &FooShared) -> Result<(), OutOfRange> {
+    if foo.port >= 1024 {
+        Ok(())
+    } else {
+        Err(OutOfRange("port"))
+    }
+}
+
+#[derive(Debug, FromSuper)]
+#[fromsuper(
+    from_type = "Bar",
+    unpack = true,
+    shared_error = true,
+    validate = "valid_shared"
+)]
+struct FooShared {
+    #[allow(dead_code)]
+    #[fromsuper(validate = "non_empty")]
+    name: String,
+    port: u16,
+}
+
+#[test]
+fn test_validate_shared_error() {
+    let bar = |name: &str, port| Bar {
+        name: Some(name.to_string()),
+        port: Some(port),
+        min: 0,
+        max: 0,
+    };
+
+    let err: MissingFields = FooShared::try_from(bar("", 8080)).unwrap_err();
+    assert_eq!(vec!["name"], err.invalid_fields().collect::<Vec<_>>());
+    assert!(err.validation_error().is_none());
+
+    let err: MissingFields = FooShared::try_from(bar("name", 80)).unwrap_err();
+    assert_eq!(0, err.invalid_fields().count());
+    assert_eq!(
+        err.to_string(),
+        "The converted validate::FooShared is invalid: port is out of range"
+    );
+    assert_eq!("port is out of range", err.source().unwrap().to_string());
+}
@@ -0,0 +1,87 @@
+use fromsuper::FromSuper;
+
+#[derive(Debug, Clone)]
+struct Bar {
+    name: Option<String>,
+    port: String,
+    tags: Vec<&'static str>,
+    level: Option<String>,
+}
+
+fn count(tags: Vec<&'static str>) -> usize {
+    tags.len()
+}
+
+mod parse {
+    pub fn port(s: String) -> Result<u16, std::num::ParseIntError> {
+        s.parse()
+    }
+
+    pub fn level(s: String) -> Result<u8, String> {
+        match s.as_str() {
+            "low" => Ok(0),
+            "high" => Ok(1),
+            _ => Err(format!("unknown level {}", s)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar", unpack = true)]
+struct Foo {
+    #[fromsuper(with = "String::into_bytes")]
+    name: Vec<u8>,
+    #[fromsuper(try_with = "parse::port", unpack = false)]
+    port: u16,
+    #[fromsuper(with = "count", unpack = false)]
+    tags: usize,
+    #[fromsuper(try_with = "parse::level")]
+    level: u8,
+}
+
+#[derive(Debug, PartialEq, FromSuper)]
+#[fromsuper(from_type = "Bar")]
+struct FooInfallible {
+    #[fromsuper(with = "count")]
+    tags: usize,
+}
+
+#[test]
+fn test_with() {
+    let bar = Bar {
+        name: Some("hello".to_string()),
+        port: "8080".to_string(),
+        tags: vec!["a", "b"],
+        level: Some("high".to_string()),
+    };
+
+    assert_eq!(
+        Foo {
+            name: b"hello".to_vec(),
+            port: 8080,
+            tags: 2,
+            level: 1,
+        },
+        bar.clone().try_into().unwrap()
+    );
+    assert_eq!(FooInfallible { tags: 2 }, bar.into());
+}
+
+#[test]
+fn test_try_with_errors() {
+    let bar = Bar {
+        name: None,
+        port: "http".to_string(),
+        tags: vec![],
+        level: Some("medium".to_string()),
+    };
+
+    // errors are collected for all fields
+    let err = Foo::try_from(bar).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Attribute(s) name of the super struct Bar not initialized; \
+         Attribute port of the super struct Bar could not be converted: invalid digit found in string; \
+         Attribute level of the super struct Bar could not be converted: unknown level medium"
+    );
+}
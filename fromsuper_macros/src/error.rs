@@ -0,0 +1,442 @@
+//! Generation of the error types returned by fallible conversions
+
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Ident, Meta, Path, Type, Visibility};
+
+/// The error type of a fallible conversion
+pub(crate) enum ErrorKind {
+    /// An error type generated specifically for the conversion
+    Generated(Ident),
+
+    /// The error type shared by all conversions, `fromsuper::MissingFields`
+    Shared,
+
+    /// An existing error type, which `fromsuper::MissingFields` is converted to
+    Custom {
+        ty: Path,
+
+        /// The function converting `fromsuper::MissingFields` to the error type
+        map_err: TokenStream,
+    },
+}
+
+impl ErrorKind {
+    /// The error type, as used for `TryFrom::Error`
+    pub(crate) fn ty(&self) -> TokenStream {
+        match self {
+            ErrorKind::Generated(ident) => quote!(#ident),
+            ErrorKind::Shared => quote!(::fromsuper::MissingFields),
+            ErrorKind::Custom { ty, .. } => quote!(#ty),
+        }
+    }
+
+    /// Convert the collected `error` to the error type, if necessary
+    pub(crate) fn finish(&self, error: TokenStream) -> TokenStream {
+        match self {
+            ErrorKind::Generated(_) | ErrorKind::Shared => error,
+            ErrorKind::Custom { map_err, .. } => quote!(#map_err(#error)),
+        }
+    }
+
+    /// An expression creating an empty error for converting from the given super type,
+    /// or the given variant of it
+    pub(crate) fn create(&self, from_type: &Type, variant: Option<&Ident>) -> TokenStream {
+        let super_type = quote!(::std::any::type_name::<#from_type>());
+        match (self, variant) {
+            (ErrorKind::Generated(ident), None) => quote!(#ident::new(#super_type)),
+            (ErrorKind::Generated(ident), Some(variant)) => {
+                quote!(#ident::new(#super_type, stringify!(#variant)))
+            }
+            (ErrorKind::Shared | ErrorKind::Custom { .. }, None) => {
+                quote!(::fromsuper::MissingFields::new(
+                    ::std::any::type_name::<Self>(),
+                    #super_type
+                ))
+            }
+            (ErrorKind::Shared | ErrorKind::Custom { .. }, Some(variant)) => {
+                quote!(::fromsuper::MissingFields::new_variant(
+                    ::std::any::type_name::<Self>(),
+                    #super_type,
+                    stringify!(#variant)
+                ))
+            }
+        }
+    }
+
+    /// An expression creating the (converted) error for a variant of the super enum that is not modeled
+    pub(crate) fn unmodeled(&self, from_type: &Type, variant: Option<&Ident>) -> TokenStream {
+        let super_type = quote!(::std::any::type_name::<#from_type>());
+        let variant = match variant {
+            Some(variant) => quote!(Some(stringify!(#variant))),
+            None => quote!(None),
+        };
+        let error = match self {
+            ErrorKind::Generated(ident) => quote!(#ident::unmodeled(#super_type, #variant)),
+            ErrorKind::Shared | ErrorKind::Custom { .. } => {
+                quote!(::fromsuper::MissingFields::unmodeled(
+                    ::std::any::type_name::<Self>(),
+                    #super_type,
+                    #variant
+                ))
+            }
+        };
+        self.finish(error)
+    }
+}
+
+/// Generate the enum with a variant for each field of the sub type,
+/// given by the variant's name, the field's name and the variant's documentation.
+pub(crate) fn field_enum(
+    ident: &Ident,
+    sub_type: &Ident,
+    vis: &Visibility,
+    variants: &[(Ident, TokenStream, String)],
+) -> TokenStream {
+    let names = variants
+        .iter()
+        .map(|(variant, name, _)| quote!(#ident::#variant => stringify!(#name)));
+    let variants = variants
+        .iter()
+        .map(|(variant, _, doc)| quote!(#[doc = #doc] #variant));
+    let doc = format!(
+        "The fields of `{}`, as referred to by the errors of its conversions.",
+        sub_type
+    );
+
+    quote!(
+        #[doc = #doc]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+        #vis enum #ident {
+            #(#variants),*
+        }
+
+        #[allow(dead_code)]
+        impl #ident {
+            /// The name of the field within the sub type.
+            pub fn name(&self) -> &'static str {
+                match *self {
+                    #(#names,)*
+                }
+            }
+        }
+
+        impl ::std::fmt::Display for #ident {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.name())
+            }
+        }
+    )
+}
+
+/// The error type of the conversion from a single super type
+pub(crate) struct ErrorType<'a> {
+    pub(crate) ident: Ident,
+    pub(crate) from_type: &'a Type,
+
+    /// The enum referring to the fields of the sub type
+    pub(crate) field_enum: &'a Ident,
+
+    /// Whether the sub type is an enum, so the error needs to refer to the super variant
+    pub(crate) is_enum: bool,
+
+    /// Whether fields can fail to be converted for other reasons than being missing
+    pub(crate) invalid: bool,
+
+    /// The sub type if the converted value is validated, so that the validation can fail
+    pub(crate) validated: Option<&'a Ident>,
+
+    /// The visibility of the error type, private by default
+    pub(crate) vis: Option<&'a Visibility>,
+
+    /// Traits to derive in addition to the default ones
+    pub(crate) derives: Vec<&'a Path>,
+
+    /// Additional attributes of the error type
+    pub(crate) attrs: &'a [Meta],
+}
+
+impl<'a> ErrorType<'a> {
+    /// Generate the definition of the error type and its trait implementations.
+    pub(crate) fn definition(&self) -> TokenStream {
+        let ErrorType {
+            ref ident,
+            from_type,
+            field_enum,
+            is_enum,
+            invalid,
+            validated,
+            vis,
+            ref derives,
+            attrs,
+        } = *self;
+
+        // Boxed errors cannot be compared, so only derive PartialEq if there are none
+        let default_derives: &[&str] = if invalid || validated.is_some() {
+            &["Debug"]
+        } else {
+            &["PartialEq", "Debug"]
+        };
+        let extra_derives = derives.iter().filter(|path| {
+            !path
+                .segments
+                .last()
+                .is_some_and(|segment| default_derives.iter().any(|d| segment.ident == d))
+        });
+        let default_derives = default_derives
+            .iter()
+            .map(|d| Ident::new(d, proc_macro2::Span::call_site()));
+        let derives = quote!(#[derive(#(#default_derives,)* #(#extra_derives),*)]);
+
+        let variant_fields = if is_enum {
+            quote!(
+                variant: Option<&'static str>,
+                unmodeled: bool,
+            )
+        } else {
+            quote!()
+        };
+
+        let invalid_field = if invalid {
+            quote!(invalid: Vec<(#field_enum, ::fromsuper::__private::Invalid)>,)
+        } else {
+            quote!()
+        };
+
+        let validation_field = if validated.is_some() {
+            quote!(validation: Option<::fromsuper::__private::Invalid>,)
+        } else {
+            quote!()
+        };
+
+        let invalid_init = match (invalid, validated) {
+            (true, Some(_)) => quote!(invalid: Vec::new(), validation: None,),
+            (true, None) => quote!(invalid: Vec::new(),),
+            (false, Some(_)) => quote!(validation: None,),
+            (false, None) => quote!(),
+        };
+
+        let constructors = if is_enum {
+            quote!(
+                fn new(super_type: &'static str, variant: &'static str) -> Self {
+                    Self { super_type, variant: Some(variant), unmodeled: false, missing: Vec::new(), #invalid_init }
+                }
+
+                fn unmodeled(super_type: &'static str, variant: Option<&'static str>) -> Self {
+                    Self { super_type, variant, unmodeled: true, missing: Vec::new(), #invalid_init }
+                }
+            )
+        } else {
+            quote!(
+                fn new(super_type: &'static str) -> Self {
+                    Self { super_type, missing: Vec::new(), #invalid_init }
+                }
+            )
+        };
+
+        let invalid_methods = if invalid {
+            quote!(
+                fn push_invalid(&mut self, field: #field_enum, invalid: ::fromsuper::__private::Invalid) {
+                    self.invalid.push((field, invalid));
+                }
+
+                /// The fields of the sub type that could not be converted.
+                pub fn invalid_fields(&self) -> impl Iterator<Item = #field_enum> + '_ {
+                    self.invalid.iter().map(|(field, _)| *field)
+                }
+            )
+        } else {
+            quote!()
+        };
+
+        let any_invalid = if invalid {
+            quote!(|| !self.invalid.is_empty())
+        } else {
+            quote!()
+        };
+
+        let validation_methods = if validated.is_some() {
+            quote!(
+                fn fail_validation(&mut self, invalid: ::fromsuper::__private::Invalid) {
+                    self.validation = Some(invalid);
+                }
+
+                /// The error of the validation of the converted value, if it failed.
+                pub fn validation_error(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+                    self.validation
+                        .as_ref()
+                        .map(::fromsuper::__private::Invalid::error)
+                }
+            )
+        } else {
+            quote!()
+        };
+
+        let display_validation = match validated {
+            Some(sub_type) => quote!(if let Some(ref validation) = self.validation {
+                return ::fromsuper::__private::fmt_validation_error(
+                    f,
+                    stringify!(#sub_type),
+                    validation,
+                );
+            }),
+            None => quote!(),
+        };
+
+        // the part of the super type the failing fields belong to
+        let super_part = if is_enum {
+            quote!(format!(
+                "variant {} of the super enum {}",
+                self.variant.unwrap_or_default(),
+                stringify!(#from_type)
+            ))
+        } else {
+            quote!(format!("the super struct {}", stringify!(#from_type)))
+        };
+
+        let display_unmodeled = if is_enum {
+            quote!(if self.unmodeled {
+                return match self.variant {
+                    Some(variant) => write!(
+                        f,
+                        "Variant {} of the super enum {} is not modeled",
+                        variant,
+                        stringify!(#from_type)
+                    ),
+                    None => write!(
+                        f,
+                        "Encountered a variant of the super enum {} that is not modeled",
+                        stringify!(#from_type)
+                    ),
+                };
+            })
+        } else {
+            quote!()
+        };
+
+        let invalid_entries = if invalid {
+            quote!(self
+                .invalid
+                .iter()
+                .map(|(field, invalid)| (field.name(), invalid)))
+        } else {
+            quote!(::std::iter::empty())
+        };
+
+        let source = match (invalid, validated) {
+            (true, Some(_)) => quote!(
+                fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+                    self.validation_error()
+                        .or_else(|| self.invalid.first().map(|(_, invalid)| invalid.error()))
+                }
+            ),
+            (true, None) => quote!(
+                fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+                    self.invalid.first().map(|(_, invalid)| invalid.error())
+                }
+            ),
+            (false, Some(_)) => quote!(
+                fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+                    self.validation_error()
+                }
+            ),
+            (false, None) => quote!(),
+        };
+
+        // only document the error type if the user did not do so via error_attr
+        let doc = if attrs.iter().any(|attr| attr.path().is_ident("doc")) {
+            quote!()
+        } else {
+            let doc = format!(
+                "The error of a failed conversion from `{}`.",
+                from_type.to_token_stream()
+            );
+            quote!(#[doc = #doc])
+        };
+
+        quote!(
+            #[allow(non_camel_case_types)]
+            #derives
+            #doc
+            #(#[#attrs])*
+            #vis struct #ident {
+                super_type: &'static str,
+                #variant_fields
+                missing: Vec<(#field_enum, &'static str)>,
+                #invalid_field
+                #validation_field
+            }
+
+            #[allow(dead_code)]
+            impl #ident {
+                #constructors
+
+                fn push(&mut self, field: #field_enum, source: &'static str) {
+                    self.missing.push((field, source));
+                }
+
+                /// The fields of the sub type that were missing in the super type.
+                pub fn missing_fields(&self) -> impl Iterator<Item = #field_enum> + '_ {
+                    self.missing.iter().map(|(field, _)| *field)
+                }
+
+                /// Whether the given field of the sub type was missing in the super type.
+                pub fn is_missing(&self, field: #field_enum) -> bool {
+                    self.missing.iter().any(|(missing, _)| *missing == field)
+                }
+
+                /// The names of the missing fields within the super type.
+                pub fn missing_sources(&self) -> impl Iterator<Item = &'static str> + '_ {
+                    self.missing.iter().map(|(_, source)| *source)
+                }
+
+                /// The full name of the super type, including its generic arguments.
+                pub fn super_type_name(&self) -> &'static str {
+                    self.super_type
+                }
+
+                #invalid_methods
+
+                #validation_methods
+
+                fn any_failed(&self) -> bool {
+                    !self.missing.is_empty() #any_invalid
+                }
+            }
+
+            impl ::std::fmt::Display for #ident {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    #display_unmodeled
+
+                    #display_validation
+
+                    let super_part = #super_part;
+
+                    ::fromsuper::__private::fmt_error(
+                        f,
+                        &super_part,
+                        &::fromsuper::ConversionError::missing_paths(self),
+                        &::fromsuper::ConversionError::invalid_paths(self),
+                    )
+                }
+            }
+
+            impl ::std::error::Error for #ident {
+                #source
+            }
+
+            impl ::fromsuper::ConversionError for #ident {
+                fn missing_paths(&self) -> Vec<::fromsuper::FieldPath> {
+                    ::fromsuper::__private::missing_paths(
+                        self.missing.iter().map(|(field, _)| field.name()),
+                        #invalid_entries,
+                    )
+                }
+
+                fn invalid_paths(&self) -> Vec<(::fromsuper::FieldPath, &(dyn ::std::error::Error + 'static))> {
+                    ::fromsuper::__private::invalid_paths(#invalid_entries)
+                }
+            }
+        )
+    }
+}
@@ -0,0 +1,572 @@
+//! Code generation for converting the fields of a struct or enum variant
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+
+use crate::{DefaultValue, FieldReceiver, RefMode, TypeWithParams};
+
+/// The options that apply to all fields converted from one super type
+pub(crate) struct Context<'a> {
+    pub(crate) super_type: &'a TypeWithParams,
+
+    /// Whether the struct-level `unpack` option is set
+    pub(crate) unpack_any: bool,
+
+    /// How the fields are taken according to the struct-level `make_refs` option
+    pub(crate) make_refs: RefMode,
+
+    /// Whether the struct-level `default_missing` option is set
+    pub(crate) default_missing: bool,
+
+    /// The enum referring to the fields of the sub type, if the error uses it
+    pub(crate) field_enum: Option<&'a syn::Ident>,
+}
+
+/// How the fields of the super type can be accessed
+#[derive(Clone, Copy)]
+pub(crate) enum Access {
+    /// As members of the super struct, which is called `value`
+    Member,
+
+    /// As variables bound by a pattern matching the super type
+    Pattern,
+}
+
+/// How the (unpacked) source value is converted to the type of the field
+pub(crate) enum Conversion<'a> {
+    /// Using the `TryFrom` implementation of the field's type
+    Nested,
+
+    /// Element by element, using the `TryFrom` implementation of the field's element type
+    NestedElements,
+
+    /// Using a custom function
+    With(&'a syn::Path),
+
+    /// Using a custom function returning a `Result`
+    TryWith(&'a syn::Path),
+}
+
+/// The generated code for converting a list of fields
+pub(crate) struct ConvertedFields {
+    /// Elements of the pattern to bind the source fields (only used with [`Access::Pattern`])
+    pub(crate) bindings: Vec<TokenStream>,
+
+    /// Statements that compute the unpacked values and record missing ones in `error`
+    pub(crate) checkers: Vec<TokenStream>,
+
+    /// Field initializers for constructing the sub type
+    pub(crate) initializers: Vec<TokenStream>,
+
+    /// Whether any of the fields can be missing, i.e. `error` is used at all
+    pub(crate) fallible: bool,
+
+    /// Whether any of the fields can fail to be converted, i.e. `error.push_invalid()` is used
+    pub(crate) invalid: bool,
+}
+
+pub(crate) fn convert_fields(
+    ctx: &Context,
+    fields: &[FieldReceiver],
+    variant: Option<&syn::Ident>,
+    access: Access,
+) -> Result<ConvertedFields, syn::Error> {
+    let mut converted = ConvertedFields {
+        bindings: Vec::new(),
+        checkers: Vec::new(),
+        initializers: Vec::new(),
+        fallible: false,
+        invalid: false,
+    };
+
+    for (index, field) in fields.iter().enumerate() {
+        let member = field.member(index);
+        let span = field.span();
+        let source_member = field.source_member(ctx.super_type, index);
+        let local = format_ident!("__fromsuper_{}", index);
+        let field_ref = match ctx.field_enum {
+            Some(field_enum) => {
+                let field_variant = field_variant(variant, field, index);
+                quote!(#field_enum::#field_variant)
+            }
+            None => quote!(stringify!(#member)),
+        };
+        let push_invalid = push_invalid(&field_ref);
+
+        if field.skip(ctx.super_type) {
+            // the field is not taken from the super type at all
+            if field.conversion(ctx.super_type)?.is_some() {
+                return Err(syn::Error::new(
+                    span,
+                    "Skipped fields cannot be converted, as they have no source value.",
+                ));
+            }
+            if field.validate(ctx.super_type).is_some() {
+                return Err(syn::Error::new(
+                    span,
+                    "Skipped fields cannot be validated, as they have no source value.",
+                ));
+            }
+
+            let value = match field.default(ctx.super_type) {
+                Some(DefaultValue::Expr(expr)) => quote_spanned!(span=> #expr),
+                Some(DefaultValue::Trait) | None => {
+                    quote_spanned!(span=> ::std::default::Default::default())
+                }
+            };
+            converted
+                .initializers
+                .push(quote_spanned!(span=> #member: #value));
+            continue;
+        }
+
+        let ty = &field.ty;
+        let cow = field.cow(ctx.super_type);
+        let make_ref = if cow {
+            if field.make_ref(ctx.super_type)?.is_some()
+                || field.conversion(ctx.super_type)?.is_some()
+                || field.unpack_elements(ctx.super_type)
+            {
+                return Err(syn::Error::new(
+                    span,
+                    "cow cannot be combined with make_ref, clone or conversions, as the source value is borrowed or owned as it is.",
+                ));
+            }
+
+            // a `Cow` borrows from a borrowed super type, and owns the value otherwise
+            match ctx.super_type.ty {
+                syn::Type::Reference(_) => RefMode::Ref,
+                _ => RefMode::Move,
+            }
+        } else {
+            field.make_ref(ctx.super_type)?.unwrap_or(ctx.make_refs)
+        };
+
+        // the `Cow` of the (unpacked) source value `v`, borrowed in its borrowed form
+        let cow_of = |v: TokenStream| match make_ref {
+            RefMode::Ref => {
+                quote_spanned!(span=> <#ty>::Borrowed(::std::borrow::Borrow::borrow(#v)))
+            }
+            _ => quote_spanned!(span=> <#ty>::Owned(#v)),
+        };
+
+        let value = match access {
+            Access::Member => match make_ref {
+                RefMode::Move => quote!(value.#source_member),
+                RefMode::Ref | RefMode::Clone => quote!(&value.#source_member),
+                RefMode::RefMut => quote!(&mut value.#source_member),
+            },
+            Access::Pattern => {
+                converted.bindings.push(match make_ref {
+                    RefMode::Move => quote!(#source_member: #local),
+                    RefMode::Ref | RefMode::Clone => quote!(#source_member: ref #local),
+                    RefMode::RefMut => quote!(#source_member: ref mut #local),
+                });
+                quote!(#local)
+            }
+        };
+
+        let unpack_result = field.unpack_result(ctx.super_type);
+        let unpack = unpack_result || ctx.unpack_any && field.unpack(ctx.super_type) != Some(false);
+        let conversion = field.conversion(ctx.super_type)?;
+        let default = field.default(ctx.super_type);
+
+        if default.is_some() && !unpack {
+            return Err(syn::Error::new(
+                span,
+                "Default values can only be used for fields that are unpacked.",
+            ));
+        }
+
+        let unpack_depth = field.unpack_depth(ctx.super_type);
+        match unpack_depth {
+            Some(0) => {
+                return Err(syn::Error::new(
+                    span,
+                    "unpack_depth needs to be at least 1.",
+                ))
+            }
+            Some(_) if !unpack || unpack_result => {
+                return Err(syn::Error::new(
+                    span,
+                    "unpack_depth can only be used for fields that are unpacked from an Option (or another unpackable type).",
+                ))
+            }
+            _ => {}
+        }
+
+        if default.is_some() && unpack_result {
+            return Err(syn::Error::new(
+                span,
+                "Default values cannot be used for fields unpacked from a Result, as its errors are not replaced.",
+            ));
+        }
+
+        // the value of the field if the unpacked source value is missing
+        let fallback = match (default, ctx.default_missing) {
+            _ if !unpack || unpack_result => None,
+            (Some(DefaultValue::Expr(expr)), _) => Some(quote_spanned!(span=> #expr)),
+            (Some(DefaultValue::Trait), _) | (None, true) => {
+                Some(quote_spanned!(span=> ::std::default::Default::default()))
+            }
+            (None, false) => None,
+        };
+
+        let mut value = value;
+        if unpack_result {
+            // errors of referenced results are cloned, so that they can be kept in the error
+            let clone_err = quote_spanned!(span=> |e| ::std::clone::Clone::clone(&*e));
+            match make_ref {
+                RefMode::Move => {}
+                RefMode::Ref => {
+                    value = quote!(::std::result::Result::as_ref(#value).map_err(#clone_err))
+                }
+                RefMode::Clone => {
+                    value = quote!(::std::result::Result::as_ref(#value)
+                        .map(::std::clone::Clone::clone)
+                        .map_err(#clone_err))
+                }
+                RefMode::RefMut => {
+                    value = quote!(::std::result::Result::as_mut(#value).map_err(#clone_err))
+                }
+            }
+            if cow {
+                let cow_of_v = cow_of(quote!(v));
+                value = quote!(::std::result::Result::map(#value, |v| #cow_of_v));
+            }
+
+            converted.checkers.push(quote_spanned! {span=>
+                let #local = match #value {
+                    ::std::result::Result::Ok(v) => ::std::option::Option::Some(v),
+                    ::std::result::Result::Err(e) => #push_invalid
+                };
+            });
+            converted.fallible = true;
+            converted.invalid = true;
+        } else if unpack {
+            let unpack_fn = match make_ref {
+                RefMode::Move => quote!(::fromsuper::Unpack::unpack),
+                RefMode::Ref | RefMode::Clone => quote!(::fromsuper::Unpack::unpack_ref),
+                RefMode::RefMut => quote!(::fromsuper::Unpack::unpack_mut),
+            };
+            value = quote!(#unpack_fn(#value));
+
+            // the value is missing if any of the layers is missing
+            for _ in 1..unpack_depth.unwrap_or(1) {
+                value = quote!(::std::option::Option::and_then(#value, #unpack_fn));
+            }
+
+            // only the unpacked value is cloned
+            if make_ref == RefMode::Clone {
+                value = quote!(::std::option::Option::map(#value, ::std::clone::Clone::clone));
+            }
+            if cow {
+                let cow_of_v = cow_of(quote!(v));
+                value = quote!(::std::option::Option::map(#value, |v| #cow_of_v));
+            }
+
+            // missing values are only recorded if there is no default value to fall back to
+            if fallback.is_some() {
+                converted.checkers.push(quote_spanned! {span=>
+                    let #local = #value;
+                });
+            } else {
+                converted.checkers.push(quote_spanned! {span=>
+                    let #local = #value;
+                    if #local.is_none() {
+                        error.push(#field_ref, stringify!(#source_member));
+                    }
+                });
+                converted.fallible = true;
+            }
+        } else if make_ref == RefMode::Clone {
+            value = quote!(::std::clone::Clone::clone(#value));
+        } else if cow {
+            value = cow_of(value);
+        }
+
+        // Without unpacking, an `Option` is borrowed as a whole, unless the field is an
+        // `Option` of a reference itself
+        if !unpack && conversion.is_none() && field.is_option_of_ref() {
+            match make_ref {
+                RefMode::Move | RefMode::Clone => {}
+                RefMode::Ref => value = quote!(::fromsuper::__private::option_ref(#value)),
+                RefMode::RefMut => value = quote!(::fromsuper::__private::option_mut(#value)),
+            }
+        }
+
+        // the conversion of the (unpacked) source value `v`
+        let unpack_elements = field.unpack_elements(ctx.super_type);
+        let try_convert = match conversion {
+            Some(Conversion::Nested | Conversion::With(_) | Conversion::TryWith(_))
+                if unpack_elements =>
+            {
+                return Err(syn::Error::new(
+                    span,
+                    "unpack_elements can only be combined with nested_elements, but not with other conversions.",
+                ));
+            }
+            Some(Conversion::Nested) => {
+                Some(quote_spanned!(span=> <#ty as ::std::convert::TryFrom<_>>::try_from(v)))
+            }
+            Some(Conversion::NestedElements) => {
+                Some(convert_elements(ty, unpack_elements, true, span))
+            }
+            None if unpack_elements => Some(convert_elements(ty, true, false, span)),
+            Some(Conversion::TryWith(function)) => Some(quote_spanned!(span=> #function(v))),
+            _ => None,
+        };
+
+        let try_converted = try_convert.is_some();
+        if let Some(try_convert) = try_convert {
+            // Conversion errors are recorded, so that all fields get checked
+            let convert = quote_spanned! {span=>
+                match #try_convert {
+                    ::std::result::Result::Ok(v) => ::std::option::Option::Some(v),
+                    ::std::result::Result::Err(e) => #push_invalid
+                }
+            };
+
+            // For nested conversions, the field type may only implement `From`, which is fine
+            converted.checkers.push(if unpack {
+                quote_spanned! {span=>
+                    #[allow(clippy::unnecessary_fallible_conversions)]
+                    let #local = match #local {
+                        ::std::option::Option::Some(v) => #convert,
+                        ::std::option::Option::None => ::std::option::Option::None,
+                    };
+                }
+            } else {
+                quote_spanned! {span=>
+                    #[allow(clippy::unnecessary_fallible_conversions)]
+                    let #local = {
+                        let v = #value;
+                        #convert
+                    };
+                }
+            });
+            converted.fallible = true;
+            converted.invalid = true;
+        }
+
+        // the custom conversion of the (unpacked and converted) value
+        let with = |v: TokenStream| match conversion {
+            Some(Conversion::With(function)) => quote_spanned!(span=> #function(#v)),
+            _ => v,
+        };
+
+        let validate = field.validate(ctx.super_type);
+        if let Some(validate) = validate {
+            // the value gets validated once it is fully converted, recording any failure
+            let checked = if unpack || try_converted {
+                quote!(#local)
+            } else {
+                quote!(::std::option::Option::Some(#value))
+            };
+            let with_v = with(quote!(v));
+            converted.checkers.push(quote_spanned! {span=>
+                let #local = match #checked {
+                    ::std::option::Option::Some(v) => {
+                        let v = #with_v;
+                        match #validate(&v) {
+                            ::std::result::Result::Ok(()) => ::std::option::Option::Some(v),
+                            ::std::result::Result::Err(e) => #push_invalid
+                        }
+                    }
+                    ::std::option::Option::None => ::std::option::Option::None,
+                };
+            });
+            converted.fallible = true;
+            converted.invalid = true;
+        }
+
+        // Missing values fall back to the default value, which is neither converted nor
+        // validated. Any other missing values have been recorded as failures.
+        let value = if validate.is_some() || unpack || try_converted {
+            let v = match validate {
+                Some(_) => quote!(v),
+                None => with(quote!(v)),
+            };
+            match fallback {
+                Some(fallback) => quote_spanned! {span=>
+                    match #local {
+                        ::std::option::Option::Some(v) => #v,
+                        ::std::option::Option::None => #fallback,
+                    }
+                },
+                None if validate.is_some() => quote_spanned!(span=> #local.unwrap()),
+                None => with(quote_spanned!(span=> #local.unwrap())),
+            }
+        } else {
+            with(value)
+        };
+
+        converted
+            .initializers
+            .push(quote_spanned!(span=> #member: #value));
+    }
+
+    Ok(converted)
+}
+
+/// Convert the source collection `v` element by element, unpacking and/or converting
+/// each element using the `TryFrom` implementation of the field's element type.
+fn convert_elements(
+    ty: &syn::Type,
+    unpack: bool,
+    nested: bool,
+    span: proc_macro2::Span,
+) -> TokenStream {
+    let element = if unpack {
+        quote_spanned! {span=>
+            match ::fromsuper::Unpack::unpack(e) {
+                ::std::option::Option::Some(e) => e,
+                ::std::option::Option::None => {
+                    return ::std::result::Result::Err(::fromsuper::__private::ElementFailure::Missing)
+                }
+            }
+        }
+    } else {
+        quote!(e)
+    };
+
+    let converted = if nested {
+        let invalid = invalid();
+        quote_spanned! {span=>
+            match <<#ty as ::fromsuper::__private::Elements>::Item as ::std::convert::TryFrom<_>>::try_from(#element) {
+                ::std::result::Result::Ok(e) => ::std::result::Result::Ok(e),
+                ::std::result::Result::Err(e) => ::std::result::Result::Err(
+                    ::fromsuper::__private::ElementFailure::Invalid(#invalid),
+                ),
+            }
+        }
+    } else {
+        quote_spanned!(span=> ::std::result::Result::Ok(#element))
+    };
+
+    quote_spanned! {span=>
+        <#ty as ::fromsuper::__private::FromElements<_>>::from_elements(v, |e| #converted)
+    }
+}
+
+/// The `Invalid` holding the error `e`, which keeps track of the failed fields of nested
+/// conversions.
+pub(crate) fn invalid() -> TokenStream {
+    quote!({
+        #[allow(unused_imports)]
+        use ::fromsuper::__private::{NestedKind as _, OpaqueKind as _};
+        (&e).fromsuper_kind().invalid(e)
+    })
+}
+
+/// Record the error `e` as the failure of the referenced field, which is left without a value.
+fn push_invalid(field_ref: &TokenStream) -> TokenStream {
+    let invalid = invalid();
+    quote!({
+        error.push_invalid(#field_ref, #invalid);
+        ::std::option::Option::None
+    })
+}
+
+/// Get the values of the super struct's fields from the fields of the sub struct, which is
+/// called `value`, packing unpacked fields again.
+/// They are returned along with the members of the super struct they belong to.
+pub(crate) fn revert_fields(
+    ctx: &Context,
+    fields: &[FieldReceiver],
+) -> Result<Vec<(syn::Member, TokenStream)>, syn::Error> {
+    let mut values = Vec::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        if field.skip(ctx.super_type) {
+            continue;
+        }
+
+        let member = field.member(index);
+        let span = field.span();
+        let source_member = field.source_member(ctx.super_type, index);
+
+        if let RefMode::Ref | RefMode::RefMut =
+            field.make_ref(ctx.super_type)?.unwrap_or(ctx.make_refs)
+        {
+            return Err(syn::Error::new(
+                span,
+                "Referenced fields cannot be converted back into the super type.",
+            ));
+        }
+
+        let value = match field.conversion(ctx.super_type)? {
+            None if field.unpack_elements(ctx.super_type) => {
+                return Err(syn::Error::new(
+                    span,
+                    "Fields converted using unpack_elements cannot be converted back into the super type. Consider skipping them.",
+                ))
+            }
+            None if field.cow(ctx.super_type) => {
+                quote_spanned!(span=> ::std::borrow::Cow::into_owned(value.#member))
+            }
+            None => quote_spanned!(span=> value.#member),
+            Some(Conversion::Nested) => {
+                quote_spanned!(span=> ::std::convert::Into::into(value.#member))
+            }
+            Some(Conversion::NestedElements)
+            | Some(Conversion::With(_))
+            | Some(Conversion::TryWith(_)) => {
+                return Err(syn::Error::new(
+                    span,
+                    "Fields converted using nested_elements, with or try_with cannot be converted back into the super type. Consider skipping them.",
+                ))
+            }
+        };
+
+        let value = if field.unpack_result(ctx.super_type) {
+            quote_spanned!(span=> ::std::result::Result::Ok(#value))
+        } else if ctx.unpack_any && field.unpack(ctx.super_type) != Some(false) {
+            let mut value = value;
+            for _ in 0..field.unpack_depth(ctx.super_type).unwrap_or(1) {
+                value = quote_spanned!(span=> ::fromsuper::Pack::pack(#value));
+            }
+            value
+        } else {
+            value
+        };
+
+        values.push((source_member, value));
+    }
+
+    Ok(values)
+}
+
+/// The variant of the field enum that refers to the given field,
+/// e.g. `SomeField` for `some_field`, or `Field0` for the first field of a tuple struct.
+/// Fields of enum variants are prefixed with the variant's name, e.g. `A0` or `BSomeField`.
+pub(crate) fn field_variant(
+    variant: Option<&syn::Ident>,
+    field: &FieldReceiver,
+    index: usize,
+) -> syn::Ident {
+    let prefix = match variant {
+        Some(variant) => variant.unraw().to_string(),
+        None => String::new(),
+    };
+
+    let name = match field.ident {
+        Some(ref ident) => ident
+            .unraw()
+            .to_string()
+            .split('_')
+            .map(|part| {
+                let mut chars = part.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            })
+            .collect(),
+        None if variant.is_some() => index.to_string(),
+        None => format!("Field{}", index),
+    };
+
+    format_ident!("{}{}", prefix, name)
+}
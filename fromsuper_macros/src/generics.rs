@@ -5,10 +5,13 @@ use syn::{Generics, Ident, Type};
 /// Collect the named lifetimes that need to be added to the impl block.
 ///
 /// The result may contain duplicates. The `'static` lifetime is ignored.
-/// An error is raised if `'_` (the anonymous lifetime) is found.
+/// An error is raised if `'_` (the anonymous lifetime) is found, or if the sub type has
+/// lifetimes unknown from the super type, except for the given ones (e.g. those only used by
+/// `Cow` fields, which own their values when converting from an owned super type).
 pub(crate) fn collect_extra_lifetimes(
     from_type: &Type,
     subtype_generics: &Generics,
+    unknown_allowed: &[syn::Lifetime],
 ) -> Result<Vec<syn::Lifetime>, syn::Error> {
     let from_lifetimes = collect_all_lifetimes(from_type);
 
@@ -17,7 +20,7 @@ pub(crate) fn collect_extra_lifetimes(
         if lifetime.ident == "_" {
             return Err(syn::Error::new(
                 lifetime.span(),
-                format!("The anonymous lifetime '_ is not supported."),
+                "The anonymous lifetime '_ is not supported.".to_string(),
             ));
         }
     }
@@ -42,13 +45,13 @@ pub(crate) fn collect_extra_lifetimes(
     // eprintln!("subtype_lifetimes: {:?}", subtype_lifetimes);
 
     for subtype_tyident in subtype_lifetimes.iter() {
-        if !from_lifetimes.contains(subtype_tyident) {
+        if !unknown_allowed.contains(subtype_tyident) && !from_lifetimes.contains(subtype_tyident) {
             return Err(syn::Error::new(
                 subtype_tyident.span(),
                 format!(
                     "Lifetime parameter '{}' is unknown from super type, which {}",
                     subtype_tyident,
-                    if from_lifetimes.len() == 0 {
+                    if from_lifetimes.is_empty() {
                         "uses none".to_string()
                     } else {
                         format!(
@@ -77,22 +80,16 @@ pub(crate) fn collect_extra_lifetimes(
 }
 
 /// Collect the identifiers of all lifetime parameters within a type definition
-fn collect_all_lifetimes(ty: &Type) -> Vec<syn::Lifetime> {
+pub(crate) fn collect_all_lifetimes(ty: &Type) -> Vec<syn::Lifetime> {
     let mut res = Vec::new();
 
     match ty {
         // TODO: impl traits
         // TODO: trait bounds
         // TODO: trait object
-        Type::Array(syn::TypeArray { elem, .. }) => {
-            return collect_all_lifetimes(elem);
-        }
-        Type::Group(syn::TypeGroup { elem, .. }) => {
-            return collect_all_lifetimes(elem);
-        }
-        Type::Paren(syn::TypeParen { elem, .. }) => {
-            return collect_all_lifetimes(elem);
-        }
+        Type::Array(syn::TypeArray { elem, .. }) => collect_all_lifetimes(elem),
+        Type::Group(syn::TypeGroup { elem, .. }) => collect_all_lifetimes(elem),
+        Type::Paren(syn::TypeParen { elem, .. }) => collect_all_lifetimes(elem),
         Type::Path(syn::TypePath { path, .. }) => {
             for segment in path.segments.iter() {
                 if let syn::PathArguments::AngleBracketed(genargs) = &segment.arguments {
@@ -110,30 +107,24 @@ fn collect_all_lifetimes(ty: &Type) -> Vec<syn::Lifetime> {
                     }
                 }
             }
-            return res;
-        }
-        Type::Ptr(syn::TypePtr { elem, .. }) => {
-            return collect_all_lifetimes(elem);
+            res
         }
+        Type::Ptr(syn::TypePtr { elem, .. }) => collect_all_lifetimes(elem),
         Type::Reference(syn::TypeReference { elem, lifetime, .. }) => {
             let mut lifetimes = collect_all_lifetimes(elem);
             if let Some(lifetime) = lifetime {
                 lifetimes.push(lifetime.clone());
             }
-            return lifetimes;
-        }
-        Type::Slice(syn::TypeSlice { elem, .. }) => {
-            return collect_all_lifetimes(elem);
+            lifetimes
         }
+        Type::Slice(syn::TypeSlice { elem, .. }) => collect_all_lifetimes(elem),
         Type::Tuple(syn::TypeTuple { elems, .. }) => {
             for elem in elems.iter() {
                 res.append(&mut collect_all_lifetimes(elem))
             }
-            return res;
-        }
-        _ => {
-            return res;
+            res
         }
+        _ => res,
     }
 }
 
@@ -1,56 +1,546 @@
-use proc_macro;
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote, quote_spanned, ToTokens};
+use quote::{format_ident, quote, ToTokens};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::{parse_macro_input, DeriveInput, Type};
 
-use darling::{ast, FromDeriveInput, FromField, FromMeta};
+use darling::{ast, FromDeriveInput, FromField, FromMeta, FromVariant};
 
+mod error;
+mod fields;
 mod generics;
+mod per_super;
 
-/// The struct that contains all the info about the to-be-derived struct.
+use per_super::PerSuper;
+
+/// The struct that contains all the info about the to-be-derived struct or enum.
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(fromsuper), supports(struct_named))]
+#[darling(attributes(fromsuper), supports(struct_named, struct_tuple, enum_any))]
 struct StructReceiver {
     /// The struct ident
     ident: syn::Ident,
 
+    /// The struct's visibility, which is also used for the field enum
+    vis: syn::Visibility,
+
     /// The type's generics
     generics: syn::Generics,
 
-    /// The body of the struct. The first type parameter is `()` because we only
-    /// accept proper structs, no enums or tuple structs.
-    data: ast::Data<(), FieldReceiver>,
+    /// The body of the struct or enum. Structs can be proper or tuple structs.
+    data: ast::Data<VariantReceiver, FieldReceiver>,
 
-    /// Option to specify the original (super) type to convert our derived type from.
-    from_type: TypeWithParams,
+    /// Option to specify the original (super) types to convert our derived type from.
+    /// It can be given multiple times, in which case a separate conversion
+    /// is derived for each super type.
+    #[darling(multiple)]
+    from_type: Vec<TypeWithParams>,
 
     /// Option to specify whether to unpack the single struct members
     unpack: Option<bool>,
 
     /// Option to specify that the sub struct should only contain references to the super struct
-    make_refs: Option<bool>,
+    make_refs: Option<RefMode>,
+
+    /// Option to clone the values from a borrowed super struct instead of referencing them
+    clone: Option<bool>,
+
+    /// Option to use default values instead of failing for all unpacked fields that are `None`
+    default_missing: Option<bool>,
+
+    /// Option to use `fromsuper::MissingFields` as the error type instead of generating one
+    shared_error: Option<bool>,
+
+    /// Option to name the generated error type, or an existing one if `map_err` is given
+    error: Option<PerSuper<syn::Path>>,
+
+    /// Option to convert `fromsuper::MissingFields` to the existing error type given by `error`
+    map_err: Option<MapErr>,
+
+    /// Option to specify the visibility of the generated error type
+    error_vis: Option<syn::Visibility>,
+
+    /// Option to derive additional traits for the generated error type
+    error_derive: Option<Punctuated<syn::Path, syn::Token![,]>>,
+
+    /// Option to add attributes to the generated error type
+    error_attr: Option<AttributeList>,
+
+    /// Option to name the enum referring to the fields in the generated error types
+    field_enum: Option<syn::Ident>,
+
+    /// Option to name the variants of the super enum that are not modeled by the sub enum
+    unmodeled_variants: Option<PerSuper<Punctuated<syn::Ident, syn::Token![,]>>>,
+
+    /// Option to also derive the conversion back into the super types
+    into_super: Option<IntoSuper>,
+
+    /// Option to generate a method writing the fields back into an existing super value
+    apply_to: Option<PerSuper<ApplyTo>>,
+
+    /// Option to validate the converted value using a function returning a `Result`
+    validate: Option<syn::Path>,
 }
 
 impl StructReceiver {
     fn try_to_tokens(&self) -> Result<TokenStream, syn::Error> {
+        if self.from_type.is_empty() {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "Missing super type. Please specify it using #[fromsuper(from_type = \"...\")].",
+            ));
+        }
+
+        if self.default_missing.unwrap_or(false) && !self.unpack.unwrap_or(false) {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "default_missing only applies to unpacked fields. Consider adding #[fromsuper(unpack = true)] to the struct.",
+            ));
+        }
+
+        if self.shared_error.unwrap_or(false) || self.map_err.is_some() {
+            let generated_options = [
+                self.error_vis.is_some(),
+                self.error_derive.is_some(),
+                self.error_attr.is_some(),
+                self.field_enum.is_some(),
+            ];
+            if generated_options.into_iter().any(|x| x) {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "The options error_vis, error_derive, error_attr and field_enum configure the generated error type, so they cannot be used together with shared_error or map_err.",
+                ));
+            }
+        }
+
+        if self.shared_error.unwrap_or(false) {
+            if self.error.is_some() || self.map_err.is_some() {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "shared_error cannot be used together with error or map_err.",
+                ));
+            }
+        } else if self.map_err.is_some() {
+            match self.error {
+                None => {
+                    return Err(syn::Error::new(
+                        proc_macro2::Span::call_site(),
+                        "map_err requires the error type to convert to, e.g. #[fromsuper(error = \"crate::MyError\", map_err)].",
+                    ));
+                }
+                Some(ref error) => {
+                    // every conversion uses the existing error type, so none may be left out
+                    if let Some(from_type) = self.from_type.iter().find(|x| error.get(x).is_none())
+                    {
+                        return Err(syn::Error::new_spanned(
+                            &from_type.ty,
+                            format!(
+                                "map_err requires the error type to convert to for each super type, but none is given for `{}`.",
+                                from_type.ty.to_token_stream()
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+
+        if let Some(ref error) = self.error {
+            per_super::check_keys(error.keys().collect(), &self.from_type)?;
+
+            // generated error types are defined next to the sub type, so they need plain names
+            if self.map_err.is_none() {
+                let paths = match error {
+                    PerSuper::All(path) => vec![path],
+                    PerSuper::Each(paths) => paths.iter().map(|(_, path)| path).collect(),
+                };
+                for path in paths {
+                    if path.get_ident().is_none() {
+                        return Err(syn::Error::new_spanned(
+                            path,
+                            "The generated error type needs a plain name. To use an existing error type instead, also specify map_err.",
+                        ));
+                    }
+                }
+
+                if let (PerSuper::All(path), true) = (error, self.from_type.len() > 1) {
+                    return Err(syn::Error::new_spanned(
+                        path,
+                        "With multiple super types, the error type needs to be named separately for each of them, e.g. error(BarV1 = \"...\", BarV2 = \"...\").",
+                    ));
+                }
+                per_super::check_unique(error, &self.from_type, "error")?;
+            }
+        }
+
+        if let Some(ref apply_to) = self.apply_to {
+            per_super::check_keys(apply_to.keys().collect(), &self.from_type)?;
+
+            if let (PerSuper::All(_), true) = (apply_to, self.from_type.len() > 1) {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "With multiple super types, the apply_to methods need to be named separately for each of them, e.g. apply_to(BarV1 = \"...\", BarV2 = \"...\").",
+                ));
+            }
+            per_super::check_unique(apply_to, &self.from_type, "apply_to")?;
+        }
+
+        if let (Some(ref validate), ast::Data::Enum(_)) = (&self.validate, &self.data) {
+            return Err(syn::Error::new_spanned(
+                validate,
+                "validate can only be used with structs. Consider validating the fields instead.",
+            ));
+        }
+
+        match self.data {
+            ast::Data::Struct(ref fields) => {
+                if self.unmodeled_variants.is_some() {
+                    return Err(syn::Error::new(
+                        proc_macro2::Span::call_site(),
+                        "unmodeled_variants can only be used with enums",
+                    ));
+                }
+
+                for keys in fields.iter().flat_map(FieldReceiver::super_keys) {
+                    per_super::check_keys(keys, &self.from_type)?;
+                }
+            }
+            ast::Data::Enum(ref variants) => {
+                for variant in variants.iter() {
+                    let field_keys = variant.fields.iter().flat_map(FieldReceiver::super_keys);
+                    for keys in variant.super_keys().into_iter().chain(field_keys) {
+                        per_super::check_keys(keys, &self.from_type)?;
+                    }
+                }
+                per_super::check_keys(per_super::keys(&self.unmodeled_variants), &self.from_type)?;
+
+                let mut others = variants.iter().filter(|v| v.other.unwrap_or(false));
+                if let Some(other) = others.next() {
+                    if let Some(second) = others.next() {
+                        return Err(syn::Error::new_spanned(
+                            &second.ident,
+                            "Only a single variant can be marked with #[fromsuper(other)].",
+                        ));
+                    }
+                    if other.fields.len() > 1 {
+                        return Err(syn::Error::new_spanned(
+                            &other.ident,
+                            "The #[fromsuper(other)] variant can have at most one field, which receives the super value.",
+                        ));
+                    }
+                    if self.unmodeled_variants.is_some() {
+                        return Err(syn::Error::new_spanned(
+                            &other.ident,
+                            "unmodeled_variants cannot be used together with an #[fromsuper(other)] variant, which receives them instead.",
+                        ));
+                    }
+                }
+            }
+        }
+
+        // borrowed super types cannot be converted back into, so they are skipped
+        // as long as there are owned ones, e.g. when converting from both `Bar` and `&'a Bar`
+        let any_owned = self
+            .from_type
+            .iter()
+            .any(|from_type| !matches!(from_type.ty, Type::Reference(_)));
+
+        let mut tokens = TokenStream::new();
+        let mut any_fallible = false;
+        for from_type in self.from_type.iter() {
+            let (impl_tokens, fallible) = self.impl_from_super(from_type)?;
+            tokens.extend(impl_tokens);
+            any_fallible |= fallible;
+
+            if let Some(ref into_super) = self.into_super {
+                if !(any_owned && matches!(from_type.ty, Type::Reference(_))) {
+                    tokens.extend(self.impl_into_super(from_type, into_super)?);
+                }
+            }
+
+            if let Some(ApplyTo(method)) = self.apply_to.as_ref().and_then(|x| x.get(from_type)) {
+                let method = method.clone().unwrap_or_else(|| format_ident!("apply_to"));
+                tokens.extend(self.impl_apply_to(from_type, &method)?);
+            }
+        }
+
+        // the generated error types refer to the fields using a shared enum,
+        // which is visible wherever they are
+        if any_fallible && !self.shared_error.unwrap_or(false) && self.map_err.is_none() {
+            tokens.extend(error::field_enum(
+                &self.field_enum(),
+                &self.ident,
+                self.error_vis.as_ref().unwrap_or(&self.vis),
+                &self.field_variants()?,
+            ));
+        }
+
+        Ok(tokens)
+    }
+
+    /// The name of the enum referring to the fields of the sub type.
+    fn field_enum(&self) -> syn::Ident {
+        match self.field_enum {
+            Some(ref field_enum) => field_enum.clone(),
+            None => format_ident!("{}Field", self.ident),
+        }
+    }
+
+    /// The variants of the field enum, along with the names of the fields they refer to
+    /// and their documentation. Fails if two fields would be referred to by the same variant.
+    fn field_variants(&self) -> Result<Vec<(syn::Ident, TokenStream, String)>, syn::Error> {
+        let fields: Vec<(Option<&syn::Ident>, usize, &FieldReceiver)> = match self.data {
+            ast::Data::Struct(ref fields) => fields
+                .iter()
+                .enumerate()
+                .map(|(index, field)| (None, index, field))
+                .collect(),
+            ast::Data::Enum(ref variants) => variants
+                .iter()
+                .filter(|v| !v.other.unwrap_or(false))
+                .flat_map(|v| {
+                    v.fields
+                        .iter()
+                        .enumerate()
+                        .map(move |(index, field)| (Some(&v.ident), index, field))
+                })
+                .collect(),
+        };
+
+        let mut field_variants: Vec<(syn::Ident, TokenStream, String)> = Vec::new();
+        for (variant, index, field) in fields {
+            let field_variant = fields::field_variant(variant, field, index);
+            if field_variants
+                .iter()
+                .any(|(other, _, _)| *other == field_variant)
+            {
+                return Err(syn::Error::new(
+                    field.span(),
+                    format!(
+                        "This field would be referred to as {}::{}, just like a previous field. Consider renaming one of them, or using shared_error.",
+                        self.field_enum(),
+                        field_variant
+                    ),
+                ));
+            }
+            let name = field.member(index).to_token_stream();
+            let doc = match variant {
+                Some(variant) => format!("The field `{}` of the variant `{}`.", name, variant),
+                None => format!("The field `{}`.", name),
+            };
+            field_variants.push((field_variant, name, doc));
+        }
+
+        Ok(field_variants)
+    }
+
+    /// The values of the sub struct's fields (called `value`) to write back into the
+    /// given super type, along with the members of the super struct they belong to.
+    /// The name of the option requiring them is used in error messages.
+    fn reverted_fields(
+        &self,
+        super_type: &TypeWithParams,
+        option: &str,
+    ) -> Result<Vec<(syn::Member, TokenStream)>, syn::Error> {
+        let fields = match self.data {
+            ast::Data::Struct(ref fields) => fields,
+            ast::Data::Enum(_) => {
+                return Err(syn::Error::new_spanned(
+                    &self.ident,
+                    format!("{} can only be used with structs.", option),
+                ))
+            }
+        };
+
+        if let (Type::Reference(_), _) | (_, None) = (&super_type.ty, super_type.base_path()) {
+            return Err(syn::Error::new_spanned(
+                &super_type.ty,
+                format!("{} requires the super type to be given as a path, e.g. `Bar`, as it cannot be written through a reference.", option),
+            ));
+        }
+
+        let ctx = fields::Context {
+            super_type,
+            unpack_any: self.unpack.unwrap_or(false),
+            make_refs: self.ref_mode()?,
+            default_missing: false,
+            field_enum: None,
+        };
+        fields::revert_fields(&ctx, &fields.fields)
+    }
+
+    /// The lifetimes of the sub type that are only used by `Cow` fields, which own their
+    /// values when converting from the given super type, so that it need not know them.
+    fn cow_lifetimes(&self, super_type: &TypeWithParams) -> Vec<syn::Lifetime> {
+        if let Type::Reference(_) = super_type.ty {
+            return Vec::new();
+        }
+
+        let fields: Vec<&FieldReceiver> = match self.data {
+            ast::Data::Struct(ref fields) => fields.iter().collect(),
+            ast::Data::Enum(ref variants) => variants
+                .iter()
+                .flat_map(|variant| variant.fields.iter())
+                .collect(),
+        };
+        let (cow_fields, other_fields): (Vec<_>, Vec<_>) =
+            fields.into_iter().partition(|field| field.cow(super_type));
+
+        let other_lifetimes: Vec<syn::Lifetime> = other_fields
+            .into_iter()
+            .flat_map(|field| generics::collect_all_lifetimes(&field.ty))
+            .collect();
+        cow_fields
+            .into_iter()
+            .flat_map(|field| generics::collect_all_lifetimes(&field.ty))
+            .filter(|lifetime| !other_lifetimes.contains(lifetime))
+            .collect()
+    }
+
+    /// How the fields are taken from the super types by default, i.e. whether they are
+    /// moved, referenced or cloned.
+    fn ref_mode(&self) -> Result<RefMode, syn::Error> {
+        match (self.make_refs, self.clone) {
+            (Some(RefMode::Ref | RefMode::RefMut), Some(true)) => Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "make_refs and clone cannot be combined, as the values are either referenced or cloned.",
+            )),
+            (_, Some(true)) => Ok(RefMode::Clone),
+            (make_refs, _) => Ok(make_refs.unwrap_or(RefMode::Move)),
+        }
+    }
+
+    /// Derive the conversion back into a single one of the super types, taking the fields
+    /// not modeled by the sub struct from a base value.
+    fn impl_into_super(
+        &self,
+        super_type: &TypeWithParams,
+        into_super: &IntoSuper,
+    ) -> Result<TokenStream, syn::Error> {
+        let from_type = &super_type.ty;
+        let mut generics = self.generics.clone();
+        let base = match into_super {
+            IntoSuper::Disabled => return Ok(quote!()),
+            IntoSuper::Default => {
+                // generic super types may only implement `Default` for some parameters
+                generics
+                    .make_where_clause()
+                    .predicates
+                    .push(syn::parse_quote!(#from_type: ::std::default::Default));
+                quote!(::std::default::Default::default())
+            }
+            IntoSuper::Base(expr) => expr.to_token_stream(),
+        };
+
+        let initializers = self
+            .reverted_fields(super_type, "into_super")?
+            .into_iter()
+            .map(|(member, value)| quote!(#member: #value));
+        // the super type is known to be a path at this point
+        let super_path = super_type.pattern_path();
+
+        let ident = &self.ident;
+        let (_, ty, _) = self.generics.split_for_impl();
+        let (_, _, wher) = generics.split_for_impl();
+        let new_generics = generics::add_types(&self.generics, super_type.params.clone());
+        let extra_lifetimes = generics::collect_extra_lifetimes(
+            from_type,
+            &self.generics,
+            &self.cow_lifetimes(super_type),
+        )?;
+        let new_generics = generics::add_lifetimes(&new_generics, extra_lifetimes);
+        let (imp, _, _) = new_generics.split_for_impl();
+
+        Ok(quote!(
+            impl #imp ::std::convert::From<#ident #ty> for #from_type #wher {
+                fn from(value: #ident #ty) -> Self {
+                    #super_path {
+                        #(#initializers,)*
+                        ..#base
+                    }
+                }
+            }
+        ))
+    }
+
+    /// Derive a method writing the sub struct's fields back into an existing value
+    /// of a single one of the super types.
+    fn impl_apply_to(
+        &self,
+        super_type: &TypeWithParams,
+        method: &syn::Ident,
+    ) -> Result<TokenStream, syn::Error> {
+        let assignments = self
+            .reverted_fields(super_type, "apply_to")?
+            .into_iter()
+            .map(|(member, value)| quote!(target.#member = #value;));
+
+        // parameters only used by the super type are parameters of the method
+        let from_type = &super_type.ty;
+        let method_params = super_type.params.iter().filter(|param| {
+            !self
+                .generics
+                .type_params()
+                .any(|type_param| type_param.ident == **param)
+        });
+        let method_generics =
+            generics::add_types(&syn::Generics::default(), method_params.cloned());
+        let extra_lifetimes = generics::collect_extra_lifetimes(
+            from_type,
+            &self.generics,
+            &self.cow_lifetimes(super_type),
+        )?;
+        let method_generics = generics::add_lifetimes(&method_generics, extra_lifetimes);
+        let (method_imp, _, _) = method_generics.split_for_impl();
+
+        let StructReceiver {
+            ref ident,
+            ref vis,
+            ref generics,
+            ..
+        } = *self;
+        let (imp, ty, wher) = generics.split_for_impl();
+        let doc = format!(
+            "Write the fields of this value back into the given `{}`.",
+            from_type.to_token_stream()
+        );
+
+        Ok(quote!(
+            impl #imp #ident #ty #wher {
+                #[doc = #doc]
+                #vis fn #method #method_imp(self, target: &mut #from_type) {
+                    let value = self;
+                    #(#assignments)*
+                }
+            }
+        ))
+    }
+
+    /// Derive the conversion from a single one of the super types,
+    /// and tell whether it is fallible, i.e. an error type is generated.
+    fn impl_from_super(
+        &self,
+        super_type: &TypeWithParams,
+    ) -> Result<(TokenStream, bool), syn::Error> {
         // get references to all our struct members so we can use them directly in quote!(...)
         let StructReceiver {
             ref ident,
             ref generics,
             ref data,
-            ref from_type,
             ref unpack,
-            ref make_refs,
+            ref default_missing,
+            ..
         } = *self;
 
-        let from_type_params = &from_type.params;
-        let from_type = &from_type.ty;
+        let from_type_params = &super_type.params;
+        let from_type = &super_type.ty;
 
         // whether to unpack any member
         let unpack_any = unpack.unwrap_or(false);
 
-        // wether to create references instead of moving the values
-        let make_refs = make_refs.unwrap_or(false);
+        // wether to create references (or clones) instead of moving the values
+        let make_refs = self.ref_mode()?;
+
+        // whether to use default values for missing fields
+        let default_missing = default_missing.unwrap_or(false);
 
         // handle generics
         let (_, ty, wher) = generics.split_for_impl();
@@ -58,7 +548,11 @@ impl StructReceiver {
         // adapt generics of impl block to include type parameters used in the
         // super struct but not in the sub struct
         let new_generics = generics::add_types(generics, from_type_params.clone());
-        let extra_lifetimes = generics::collect_extra_lifetimes(from_type, generics)?;
+        let extra_lifetimes = generics::collect_extra_lifetimes(
+            from_type,
+            generics,
+            &self.cow_lifetimes(super_type),
+        )?;
         let new_generics = generics::add_lifetimes(&new_generics, extra_lifetimes);
         let (imp, _, _) = new_generics.split_for_impl();
 
@@ -70,182 +564,721 @@ impl StructReceiver {
         // eprintln!("wher: {:?}", wher);
         // eprintln!("");
 
-        let fields = data
-            .as_ref()
-            .take_struct()
-            .expect("Should never be enum")
-            .fields;
-
-        return Ok(if unpack_any {
-            // Implement TryFrom
-
-            let error_type = format_ident!(
-                "{}FromSuperError_{}",
-                ident,
-                from_type
-                    .to_token_stream()
-                    .to_string()
-                    .chars()
-                    .filter(|c| c.is_alphanumeric())
-                    .collect::<String>()
-            );
+        if !unpack_any {
+            // make sure we're not expected to unpack anything
+            let fields: Vec<&FieldReceiver> = match data {
+                ast::Data::Struct(fields) => fields.iter().collect(),
+                ast::Data::Enum(variants) => {
+                    variants.iter().flat_map(|v| v.fields.iter()).collect()
+                }
+            };
+            for field in fields {
+                if let Some(true) = field.unpack(super_type) {
+                    return Err(
+                        syn::Error::new(
+                            proc_macro2::Span::call_site(), // TODO
+                            "Unpacking single fields requires that the whole struct be unpacked. Consider adding #]fromstruct(unpack = true)] to the struct.")
+                    );
+                }
+            }
+        }
 
-            // code to check if unwrap will be successful
-            let unwrap_checkers = fields
-                .iter()
-                .map(|field| {
-                    let field_ident = field.ident.as_ref().unwrap();
-                    let span = field_ident.span();
-                    let source_ident = field.rename_from.as_ref().unwrap_or(field_ident);
+        let error_path = self.error.as_ref().and_then(|x| x.get(super_type));
+        let error_kind = if self.shared_error.unwrap_or(false) {
+            error::ErrorKind::Shared
+        } else if let (Some(MapErr(map_err)), Some(error_path)) = (&self.map_err, error_path) {
+            error::ErrorKind::Custom {
+                ty: error_path.clone(),
+                map_err: match map_err {
+                    Some(map_err) => map_err.to_token_stream(),
+                    None => quote!(::std::convert::From::from),
+                },
+            }
+        } else {
+            error::ErrorKind::Generated(match error_path.and_then(syn::Path::get_ident) {
+                Some(error_type) => error_type.clone(),
+                None => format_ident!(
+                    "{}FromSuperError_{}",
+                    ident,
+                    from_type
+                        .to_token_stream()
+                        .to_string()
+                        .chars()
+                        .filter(|c| c.is_alphanumeric())
+                        .collect::<String>()
+                ),
+            })
+        };
+        let error_type = error_kind.ty();
 
-                    if let Some(false) = field.unpack {
-                        quote!()
-                    } else {
-                        quote_spanned! {span=>
-                            if value.#source_ident.is_none() {
-                                error.push(stringify!(#field_ident));
-                            }
-                        }
-                    }
-                })
-                .collect::<Vec<_>>();
+        // the shared error refers to the fields by their names only
+        let field_enum = self.field_enum();
+        let ctx = fields::Context {
+            super_type,
+            unpack_any,
+            make_refs,
+            default_missing,
+            field_enum: match error_kind {
+                error::ErrorKind::Generated(_) => Some(&field_enum),
+                error::ErrorKind::Shared | error::ErrorKind::Custom { .. } => None,
+            },
+        };
 
-            let initializers = fields
-                .iter()
-                .map(|field| {
-                    let field_ident = field.ident.as_ref().unwrap();
-                    let span = field_ident.span();
-                    let source_ident = field.rename_from.as_ref().unwrap_or(field_ident);
-
-                    if let Some(false) = field.unpack {
-                        if make_refs {
-                            quote_spanned!(span=> #field_ident: &value.#source_ident)
-                        }
-                        else {
-                            quote_spanned!(span=> #field_ident: value.#source_ident)
-                        }
+        let (implementation, fallible, invalid) = match data {
+            ast::Data::Struct(fields) => {
+                let fields::ConvertedFields {
+                    checkers,
+                    initializers,
+                    fallible,
+                    invalid,
+                    ..
+                } = fields::convert_fields(&ctx, &fields.fields, None, fields::Access::Member)?;
+
+                // validating the converted value needs an error to report its failure
+                let fallible = fallible || self.validate.is_some();
+
+                if unpack_any || fallible {
+                    // Implement TryFrom
+
+                    let error_check = if fallible {
+                        let new_error = error_kind.create(from_type, None);
+                        let finished_error = error_kind.finish(quote!(error));
+                        quote!(
+                            let mut error = #new_error;
+
+                            #(#checkers)*
+
+                            if error.any_failed() {
+                                return Err(#finished_error)
+                            }
+                        )
                     } else {
-                        if make_refs {
-                            quote_spanned!(span=> #field_ident: value.#source_ident.as_ref().unwrap())
-                        }
-                        else {
-                            quote_spanned!(span=> #field_ident: value.#source_ident.unwrap())
+                        // unpacked values can still be checked for falling back to defaults
+                        quote!(#(#checkers)*)
+                    };
+
+                    let validation = match self.validate {
+                        Some(ref validate) => {
+                            let finished_error = error_kind.finish(quote!(error));
+                            let invalid = fields::invalid();
+                            quote!(
+                                if let ::std::result::Result::Err(e) = #validate(&converted) {
+                                    error.fail_validation(#invalid);
+                                    return Err(#finished_error)
+                                }
+                            )
                         }
-                    }
-                })
-                .collect::<Vec<_>>();
+                        None => quote!(),
+                    };
 
-            quote!(
-                impl #imp ::std::convert::TryFrom<#from_type> for #ident #ty #wher {
-                    type Error = #error_type;
+                    (
+                        quote!(
+                            impl #imp ::std::convert::TryFrom<#from_type> for #ident #ty #wher {
+                                type Error = #error_type;
 
-                    fn try_from(value: #from_type) -> ::std::result::Result<Self, Self::Error> {
-                        let mut error = #error_type::new();
+                                fn try_from(value: #from_type) -> ::std::result::Result<Self, Self::Error> {
+                                    #error_check
 
-                        #(#unwrap_checkers)*
+                                    let converted = Self {
+                                        #(#initializers),*
+                                    };
 
-                        if (error.any_missing()) {
-                            return Err(error)
-                        }
+                                    #validation
 
-                        Ok( Self {
-                            #(#initializers),*
-                        } )
-                    }
-                }
+                                    Ok(converted)
+                                }
+                            }
+                        ),
+                        true,
+                        invalid,
+                    )
+                } else {
+                    // Implement From
 
-                #[allow(non_camel_case_types)]
-                #[derive(PartialEq, Debug)]
-                struct #error_type {
-                    missing: Vec<&'static str>,
+                    (
+                        quote!(
+                            impl #imp ::std::convert::From<#from_type> for #ident #ty #wher {
+                                fn from(value: #from_type) -> Self {
+                                    Self {
+                                        #(#initializers),*
+                                    }
+                                }
+                            }
+                        ),
+                        false,
+                        false,
+                    )
                 }
+            }
+            ast::Data::Enum(variants) => {
+                let super_path = super_type.pattern_path().ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        from_type,
+                        "Enums can only be converted from super types given as a path, e.g. `Bar` or `&'a Bar`.",
+                    )
+                })?;
 
-                impl #error_type {
-                    fn new() -> Self { Self { missing: Vec::new() }}
+                // Match on the enum itself if it is referenced. This way, fields can be
+                // bound by value (i.e. copied), as well as by reference.
+                let scrutinee = if let Type::Reference(_) = from_type {
+                    quote!(*value)
+                } else {
+                    quote!(value)
+                };
 
-                    fn push(&mut self, missing: &'static str) {
-                        self.missing.push(missing);
-                    }
+                let mut arms = Vec::new();
+                let mut other = None;
+                let mut any_fallible = false;
+                let mut any_invalid = false;
+
+                for variant in variants.iter() {
+                    let variant_ident = &variant.ident;
 
-                    fn any_missing(&self) -> bool {
-                        self.missing.len() > 0
+                    if variant.other.unwrap_or(false) {
+                        // the catch-all variant receives the super value as is
+                        other = Some(match variant.fields.iter().next() {
+                            None => quote!(Self::#variant_ident {}),
+                            Some(field) => {
+                                let member = field.member(0);
+                                quote!(Self::#variant_ident { #member: value })
+                            }
+                        });
+                        continue;
                     }
-                }
 
-                impl ::std::fmt::Display for #error_type {
-                    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
-                        write!(f, "Attribute(s) ")?;
+                    let source_variant = variant.source_ident(super_type);
+                    let fields::ConvertedFields {
+                        bindings,
+                        checkers,
+                        initializers,
+                        fallible,
+                        invalid,
+                    } = fields::convert_fields(
+                        &ctx,
+                        &variant.fields.fields,
+                        Some(variant_ident),
+                        fields::Access::Pattern,
+                    )?;
+                    any_fallible |= fallible;
+                    any_invalid |= invalid;
+
+                    let error_check = if fallible {
+                        let new_error = error_kind.create(from_type, Some(source_variant));
+                        let finished_error = error_kind.finish(quote!(error));
+                        quote!(
+                            let mut error = #new_error;
 
-                        for (i, missing) in self.missing.iter().enumerate() {
-                            write!(f, "{}", missing)?;
-                            if i+1 < self.missing.len() {
-                                write!(f, ", ")?;
+                            #(#checkers)*
+
+                            if error.any_failed() {
+                                return Err(#finished_error)
+                            }
+                        )
+                    } else {
+                        // unpacked values can still be checked for falling back to defaults
+                        quote!(#(#checkers)*)
+                    };
+
+                    arms.push(quote!(
+                        #super_path::#source_variant { #(#bindings,)* .. } => {
+                            #error_check
+
+                            Self::#variant_ident {
+                                #(#initializers),*
                             }
                         }
+                    ));
+                }
+
+                let fallible = unpack_any || any_fallible || other.is_none();
 
-                        write!(f, " of the super struct {} not initialized", stringify!(#from_type))?;
-                        Ok(())
+                if let Some(other) = other {
+                    arms.push(quote!(
+                        #[allow(unreachable_patterns)]
+                        _ => #other
+                    ));
+                } else if let Some(unmodeled) = self
+                    .unmodeled_variants
+                    .as_ref()
+                    .and_then(|x| x.get(super_type))
+                {
+                    // With an explicit list of unmodeled variants, the match stays exhaustive
+                    // and the compiler makes sure that no super variant is forgotten.
+                    for unmodeled_ident in unmodeled.iter() {
+                        let error = error_kind.unmodeled(from_type, Some(unmodeled_ident));
+                        arms.push(quote!(
+                            #super_path::#unmodeled_ident { .. } => {
+                                return Err(#error)
+                            }
+                        ));
                     }
+                } else {
+                    let unmodeled_error = error_kind.unmodeled(from_type, None);
+                    arms.push(quote!(
+                        #[allow(unreachable_patterns)]
+                        _ => return Err(#unmodeled_error)
+                    ));
                 }
 
-                impl ::std::error::Error for #error_type { }
-            )
-        } else {
-            // Implement From
+                if fallible {
+                    // Implement TryFrom
 
-            // make sure we're not expected to unpack anything
-            for field in fields.iter() {
-                if let Some(true) = field.unpack {
-                    return Err(
-                        syn::Error::new(
-                            proc_macro2::Span::call_site(), // TODO
-                            "Unpacking single fields requires that the whole struct be unpacked. Consider adding #]fromstruct(unpack = true)] to the struct.")
-                    );
+                    (
+                        quote!(
+                            impl #imp ::std::convert::TryFrom<#from_type> for #ident #ty #wher {
+                                type Error = #error_type;
+
+                                fn try_from(value: #from_type) -> ::std::result::Result<Self, Self::Error> {
+                                    Ok(match #scrutinee {
+                                        #(#arms)*
+                                    })
+                                }
+                            }
+                        ),
+                        true,
+                        any_invalid,
+                    )
+                } else {
+                    // Implement From
+
+                    (
+                        quote!(
+                            impl #imp ::std::convert::From<#from_type> for #ident #ty #wher {
+                                fn from(value: #from_type) -> Self {
+                                    match #scrutinee {
+                                        #(#arms)*
+                                    }
+                                }
+                            }
+                        ),
+                        false,
+                        false,
+                    )
                 }
             }
+        };
 
-            let initializers = fields
-                .iter()
-                .map(|field| {
-                    let field_ident = field.ident.as_ref().unwrap();
-                    let span = field_ident.span();
-                    let source_ident = field.rename_from.as_ref().unwrap_or(field_ident);
+        let error_definition = match error_kind {
+            error::ErrorKind::Generated(ref error_ident) if fallible => error::ErrorType {
+                ident: error_ident.clone(),
+                from_type,
+                field_enum: &field_enum,
+                is_enum: matches!(data, ast::Data::Enum(_)),
+                invalid,
+                validated: self.validate.as_ref().map(|_| ident),
+                vis: self.error_vis.as_ref(),
+                derives: self.error_derive.iter().flatten().collect(),
+                attrs: self.error_attr.as_ref().map_or(&[], |x| &x.0),
+            }
+            .definition(),
+            _ => quote!(),
+        };
 
-                    quote_spanned!(span=> #field_ident: value.#source_ident)
-                })
-                .collect::<Vec<_>>();
+        Ok((quote!(#implementation #error_definition), fallible))
+    }
+}
 
-            quote!(
-                impl #imp ::std::convert::From<#from_type> for #ident #ty #wher {
-                    fn from(value: #from_type) -> Self {
-                        Self {
-                            #(#initializers),*
-                        }
-                    }
-                }
-            )
-        });
+/// The handler for each variant within the provided enum
+#[derive(Debug, FromVariant)]
+#[darling(attributes(fromsuper))]
+struct VariantReceiver {
+    /// The variant ident
+    ident: syn::Ident,
+
+    /// The fields of the variant, which can be named, unnamed or absent.
+    fields: ast::Fields<FieldReceiver>,
+
+    /// Option to take this variant from a differently-named variant of the super enum
+    rename_from: Option<PerSuper<syn::Ident>>,
+
+    /// Option to mark this variant as the catch-all for any super variant not modeled otherwise
+    other: Option<bool>,
+}
+
+impl VariantReceiver {
+    /// The variant of the given super type that this variant is converted from.
+    fn source_ident(&self, from_type: &TypeWithParams) -> &syn::Ident {
+        self.rename_from
+            .as_ref()
+            .and_then(|x| x.get(from_type))
+            .unwrap_or(&self.ident)
+    }
+
+    /// All the super type keys used in per-super options of this variant.
+    fn super_keys(&self) -> Vec<Vec<&syn::Path>> {
+        vec![per_super::keys(&self.rename_from)]
     }
 }
 
-/// The handler for each field within the provided struct
+/// The handler for each field within the provided struct or enum variant
 #[derive(Debug, FromField)]
 #[darling(attributes(fromsuper))]
 struct FieldReceiver {
     /// Get the ident of the field. This is an Option to accommodate tuples or
-    /// tuple structs (`None` in this case). However, this cannot happen in our
-    /// case as we only allow normal structs.
+    /// tuple structs (`None` in this case).
     ident: Option<syn::Ident>,
 
     /// This magic field name pulls the type from the input.
-    #[allow(dead_code)]
     ty: syn::Type,
 
     /// Option to specifically disable unpacking this field.
-    unpack: Option<bool>,
+    unpack: Option<PerSuper<bool>>,
 
-    /// Option to take this field's value from a differently-named source field
-    rename_from: Option<syn::Ident>,
+    /// Option to unpack this field's source value as a `Result` instead of an `Option`
+    unpack_result: Option<PerSuper<bool>>,
+
+    /// Option to unpack several layers of `Option`s (or other unpackable types)
+    unpack_depth: Option<PerSuper<usize>>,
+
+    /// Option to unpack the elements of the source collection, e.g. of a `Vec<Option<T>>`
+    unpack_elements: Option<PerSuper<bool>>,
+
+    /// Option to take this field's value from a differently-named source field,
+    /// or from a field at a different position for tuple structs
+    rename_from: Option<PerSuper<SourceMember>>,
+
+    /// Option to convert the source value using the field type's own `TryFrom` implementation
+    nested: Option<PerSuper<bool>>,
+
+    /// Option to convert the source collection element by element, using the `TryFrom`
+    /// implementation of the field's element type
+    nested_elements: Option<PerSuper<bool>>,
+
+    /// Option to convert the source value using a custom function
+    with: Option<PerSuper<syn::Path>>,
+
+    /// Option to convert the source value using a custom function returning a `Result`
+    try_with: Option<PerSuper<syn::Path>>,
+
+    /// Option to validate the converted value using a function returning a `Result`
+    validate: Option<PerSuper<syn::Path>>,
+
+    /// Option to use a default value if the unpacked source value is `None`,
+    /// or for skipped fields
+    default: Option<PerSuper<DefaultValue>>,
+
+    /// Option to not take this field from the super type, but initialize it with its default value
+    skip: Option<PerSuper<bool>>,
+
+    /// Option to make a reference to the source value, overriding the struct-level `make_refs`
+    make_ref: Option<PerSuper<RefMode>>,
+
+    /// Option to clone the source value from a borrowed super type, or copy it if false
+    clone: Option<PerSuper<bool>>,
+
+    /// Option to borrow (from a borrowed super type) or own the source value in a `Cow`
+    cow: Option<PerSuper<bool>>,
+}
+
+impl FieldReceiver {
+    /// The member to access this field by, given its position.
+    fn member(&self, index: usize) -> syn::Member {
+        match self.ident {
+            Some(ref ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(syn::Index {
+                index: index as u32,
+                span: self.span(),
+            }),
+        }
+    }
+
+    /// The span to attribute this field's generated code to.
+    fn span(&self) -> proc_macro2::Span {
+        match self.ident {
+            Some(ref ident) => ident.span(),
+            None => self.ty.span(),
+        }
+    }
+
+    /// Whether to unpack this field when converting from the given super type.
+    fn unpack(&self, from_type: &TypeWithParams) -> Option<bool> {
+        self.unpack.as_ref().and_then(|x| x.get(from_type)).copied()
+    }
+
+    /// Whether to unpack this field's source value as a `Result` when converting from the given
+    /// super type, which does not require the whole struct to be unpacked.
+    fn unpack_result(&self, from_type: &TypeWithParams) -> bool {
+        self.unpack_result.as_ref().and_then(|x| x.get(from_type)) == Some(&true)
+    }
+
+    /// Whether to unpack the elements of this field's source collection.
+    fn unpack_elements(&self, from_type: &TypeWithParams) -> bool {
+        self.unpack_elements.as_ref().and_then(|x| x.get(from_type)) == Some(&true)
+    }
+
+    /// How many layers of the source value to unpack, if specified for the field.
+    fn unpack_depth(&self, from_type: &TypeWithParams) -> Option<usize> {
+        self.unpack_depth
+            .as_ref()
+            .and_then(|x| x.get(from_type))
+            .copied()
+    }
+
+    /// Whether to make a reference to (or a clone of) this field's source value,
+    /// if specified for the field.
+    fn make_ref(&self, from_type: &TypeWithParams) -> Result<Option<RefMode>, syn::Error> {
+        let make_ref = self.make_ref.as_ref().and_then(|x| x.get(from_type));
+        let clone = self.clone.as_ref().and_then(|x| x.get(from_type));
+
+        Ok(match (make_ref, clone) {
+            (make_ref, None) => make_ref.copied(),
+            (None, Some(true)) => Some(RefMode::Clone),
+            (None, Some(false)) => Some(RefMode::Move),
+            (Some(_), Some(_)) => {
+                return Err(syn::Error::new(
+                    self.span(),
+                    "Only one of make_ref and clone can be used for a field.",
+                ))
+            }
+        })
+    }
+
+    /// Whether this field is a `Cow` borrowing its source value from a borrowed super type,
+    /// or owning it otherwise.
+    fn cow(&self, from_type: &TypeWithParams) -> bool {
+        self.cow.as_ref().and_then(|x| x.get(from_type)) == Some(&true)
+    }
+
+    /// Whether this field is not taken from the given super type.
+    /// `PhantomData` fields are skipped unless specified otherwise.
+    fn skip(&self, from_type: &TypeWithParams) -> bool {
+        match self.skip.as_ref().and_then(|x| x.get(from_type)) {
+            Some(skip) => *skip,
+            None => match self.ty {
+                Type::Path(syn::TypePath { ref path, .. }) => path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "PhantomData"),
+                _ => false,
+            },
+        }
+    }
+
+    /// Whether this field is an `Option` of a reference, e.g. `Option<&'a str>`,
+    /// which is borrowed from an `Option` of the source type when making references.
+    fn is_option_of_ref(&self) -> bool {
+        let segment = match self.ty {
+            Type::Path(syn::TypePath { ref path, .. }) => match path.segments.last() {
+                Some(segment) if segment.ident == "Option" => segment,
+                _ => return false,
+            },
+            _ => return false,
+        };
+        match segment.arguments {
+            syn::PathArguments::AngleBracketed(ref args) => matches!(
+                args.args.first(),
+                Some(syn::GenericArgument::Type(Type::Reference(_)))
+            ),
+            _ => false,
+        }
+    }
+
+    /// The default value to use if this field's source value is `None`, or it is skipped.
+    fn default(&self, from_type: &TypeWithParams) -> Option<&DefaultValue> {
+        self.default.as_ref().and_then(|x| x.get(from_type))
+    }
+
+    /// How to convert this field's value when converting from the given super type.
+    fn conversion(
+        &self,
+        from_type: &TypeWithParams,
+    ) -> Result<Option<fields::Conversion<'_>>, syn::Error> {
+        let nested = self.nested.as_ref().and_then(|x| x.get(from_type)) == Some(&true);
+        let nested_elements =
+            self.nested_elements.as_ref().and_then(|x| x.get(from_type)) == Some(&true);
+        let with = self.with.as_ref().and_then(|x| x.get(from_type));
+        let try_with = self.try_with.as_ref().and_then(|x| x.get(from_type));
+
+        Ok(match (nested, nested_elements, with, try_with) {
+            (false, false, None, None) => None,
+            (true, false, None, None) => Some(fields::Conversion::Nested),
+            (false, true, None, None) => Some(fields::Conversion::NestedElements),
+            (false, false, Some(function), None) => Some(fields::Conversion::With(function)),
+            (false, false, None, Some(function)) => Some(fields::Conversion::TryWith(function)),
+            _ => return Err(syn::Error::new(
+                self.span(),
+                "Only one of nested, nested_elements, with and try_with can be used for a field.",
+            )),
+        })
+    }
+
+    /// The function to validate this field's converted value with, if any.
+    fn validate(&self, from_type: &TypeWithParams) -> Option<&syn::Path> {
+        self.validate.as_ref().and_then(|x| x.get(from_type))
+    }
+
+    /// The member of the given super type that this field's value is taken from.
+    fn source_member(&self, from_type: &TypeWithParams, index: usize) -> syn::Member {
+        match self.rename_from.as_ref().and_then(|x| x.get(from_type)) {
+            Some(SourceMember(member)) => member.clone(),
+            None => self.member(index),
+        }
+    }
+
+    /// The super type keys used in the per-super options of this field, for each option.
+    fn super_keys(&self) -> Vec<Vec<&syn::Path>> {
+        vec![
+            per_super::keys(&self.unpack),
+            per_super::keys(&self.unpack_result),
+            per_super::keys(&self.unpack_depth),
+            per_super::keys(&self.unpack_elements),
+            per_super::keys(&self.rename_from),
+            per_super::keys(&self.nested),
+            per_super::keys(&self.nested_elements),
+            per_super::keys(&self.with),
+            per_super::keys(&self.try_with),
+            per_super::keys(&self.validate),
+            per_super::keys(&self.default),
+            per_super::keys(&self.skip),
+            per_super::keys(&self.make_ref),
+            per_super::keys(&self.clone),
+            per_super::keys(&self.cow),
+        ]
+    }
+}
+
+/// A field of the super type to take a value from, given either by its name
+/// or, for tuple structs, by its index (e.g. `"2"`).
+#[derive(Debug)]
+struct SourceMember(syn::Member);
+
+impl FromMeta for SourceMember {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        let member = syn::parse_str(value).map_err(|_| darling::Error::unknown_value(value))?;
+        Ok(SourceMember(member))
+    }
+}
+
+/// A list of attributes given as `name(attr1, attr2 = "...")`,
+/// which are added as `#[attr1]` and `#[attr2 = "..."]`.
+#[derive(Debug)]
+struct AttributeList(Vec<syn::Meta>);
+
+impl FromMeta for AttributeList {
+    fn from_list(items: &[syn::NestedMeta]) -> darling::Result<Self> {
+        let mut attrs = Vec::new();
+        for item in items {
+            match item {
+                syn::NestedMeta::Meta(meta) => attrs.push(meta.clone()),
+                syn::NestedMeta::Lit(lit) => {
+                    return Err(darling::Error::unexpected_lit_type(lit));
+                }
+            }
+        }
+        Ok(AttributeList(attrs))
+    }
+}
+
+/// The function to convert `fromsuper::MissingFields` to a custom error type.
+/// If given as a bare `map_err`, the error type's `From` implementation is used.
+#[derive(Debug)]
+struct MapErr(Option<syn::Path>);
+
+impl FromMeta for MapErr {
+    fn from_word() -> darling::Result<Self> {
+        Ok(MapErr(None))
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        let path = syn::parse_str(value).map_err(|_| darling::Error::unknown_value(value))?;
+        Ok(MapErr(Some(path)))
+    }
+}
+
+/// The default value of a field, given either as a bare `default`,
+/// using the type's `Default` implementation, or as an expression (`default = "..."`).
+#[derive(Debug)]
+enum DefaultValue {
+    Trait,
+    Expr(Box<syn::Expr>),
+}
+
+impl FromMeta for DefaultValue {
+    fn from_word() -> darling::Result<Self> {
+        Ok(DefaultValue::Trait)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        let expr = syn::parse_str(value).map_err(|_| darling::Error::unknown_value(value))?;
+        Ok(DefaultValue::Expr(Box::new(expr)))
+    }
+}
+
+/// How the fields are taken from the super type, given either as a bool,
+/// as a bare `make_refs` (shared references), or as `make_refs = "mut"`.
+/// Cloning is set using the separate `clone` options instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RefMode {
+    /// Move (or copy) the values
+    Move,
+    /// Make shared references to the values
+    Ref,
+    /// Make mutable references to the values
+    RefMut,
+    /// Clone the referenced values
+    Clone,
+}
+
+impl FromMeta for RefMode {
+    fn from_word() -> darling::Result<Self> {
+        Ok(RefMode::Ref)
+    }
+
+    fn from_bool(value: bool) -> darling::Result<Self> {
+        Ok(if value { RefMode::Ref } else { RefMode::Move })
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "mut" => Ok(RefMode::RefMut),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
+/// The base value to take the remaining fields of the super struct from when converting
+/// back into it, given either as a bool (or bare `into_super`), using the super struct's
+/// `Default` implementation, or as an expression (`into_super = "..."`).
+#[derive(Debug)]
+enum IntoSuper {
+    Disabled,
+    Default,
+    Base(Box<syn::Expr>),
+}
+
+impl FromMeta for IntoSuper {
+    fn from_word() -> darling::Result<Self> {
+        Ok(IntoSuper::Default)
+    }
+
+    fn from_bool(value: bool) -> darling::Result<Self> {
+        Ok(if value {
+            IntoSuper::Default
+        } else {
+            IntoSuper::Disabled
+        })
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        let expr = syn::parse_str(value).map_err(|_| darling::Error::unknown_value(value))?;
+        Ok(IntoSuper::Base(Box::new(expr)))
+    }
+}
+
+/// The name of the method writing the fields back into an existing super value,
+/// which is `apply_to` if given as a bare `apply_to`.
+#[derive(Debug)]
+struct ApplyTo(Option<syn::Ident>);
+
+impl FromMeta for ApplyTo {
+    fn from_word() -> darling::Result<Self> {
+        Ok(ApplyTo(None))
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        let ident = syn::parse_str(value).map_err(|_| darling::Error::unknown_value(value))?;
+        Ok(ApplyTo(Some(ident)))
+    }
 }
 
 /// A custom `Type` wrapper that additionally holds which contained generic types
@@ -259,13 +1292,55 @@ struct TypeWithParams {
     params: Vec<syn::Ident>,
 }
 
+impl TypeWithParams {
+    /// Get the path of the (possibly referenced) type, if it is given as one.
+    fn base_path(&self) -> Option<&syn::Path> {
+        let mut ty = &self.ty;
+        loop {
+            ty = match ty {
+                Type::Reference(syn::TypeReference { elem, .. }) => elem,
+                Type::Paren(syn::TypeParen { elem, .. }) => elem,
+                Type::Group(syn::TypeGroup { elem, .. }) => elem,
+                Type::Path(syn::TypePath { path, .. }) => return Some(path),
+                _ => return None,
+            }
+        }
+    }
+
+    /// Get the path of the type without any generic arguments, as used in patterns.
+    fn pattern_path(&self) -> Option<syn::Path> {
+        let mut path = self.base_path()?.clone();
+        for segment in path.segments.iter_mut() {
+            segment.arguments = syn::PathArguments::None;
+        }
+        Some(path)
+    }
+
+    /// Check whether a per-super option key (e.g. `BarV1`) refers to this type.
+    fn matches_key(&self, key: &syn::Path) -> bool {
+        let path = match self.base_path() {
+            Some(path) => path,
+            None => return false,
+        };
+        let skip = match path.segments.len().checked_sub(key.segments.len()) {
+            Some(skip) => skip,
+            None => return false,
+        };
+        path.segments
+            .iter()
+            .skip(skip)
+            .zip(key.segments.iter())
+            .all(|(a, b)| a.ident == b.ident)
+    }
+}
+
 /// Find types specified as free arguments, and remove the preceding `#` signs.
 fn parse_hashmark_types(s: &str) -> darling::Result<(Vec<syn::Ident>, String)> {
     let mut s = s;
     let mut new_s = String::new();
     let mut params: Vec<syn::Ident> = Vec::new();
 
-    while s.len() > 0 {
+    while !s.is_empty() {
         match s.find('#') {
             None => {
                 new_s.push_str(s);
@@ -278,7 +1353,7 @@ fn parse_hashmark_types(s: &str) -> darling::Result<(Vec<syn::Ident>, String)> {
                     .skip(1)
                     .take_while(char::is_ascii_alphanumeric)
                     .collect();
-                if ident.len() == 0 {
+                if ident.is_empty() {
                     return Err(darling::Error::custom(
                         "hash mark without following type parameter name",
                     ));
@@ -0,0 +1,156 @@
+//! Options that can be specified differently for each super type
+
+use darling::FromMeta;
+use quote::ToTokens;
+use syn::{NestedMeta, Path};
+
+use crate::TypeWithParams;
+
+/// An option value that either applies to all super types,
+/// or is given separately for some of them.
+///
+/// It is parsed either from a single value (`rename_from = "x"`) or from a list
+/// of values keyed by the super type (`rename_from(BarV1 = "x", BarV2 = "y")`).
+/// A key matches a super type if it equals the trailing segments of its path,
+/// so `BarV1` matches `crate::BarV1<#T>`, as well as `&'a BarV1`.
+/// If several keys match, the most specific one (with the most segments) applies,
+/// so `v2::Bar` takes precedence over `Bar` for the super type `v2::Bar`.
+#[derive(Debug)]
+pub(crate) enum PerSuper<T> {
+    All(T),
+    Each(Vec<(Path, T)>),
+}
+
+impl<T> PerSuper<T> {
+    /// Get the value that applies to the given super type, if any.
+    pub(crate) fn get(&self, from_type: &TypeWithParams) -> Option<&T> {
+        match self {
+            PerSuper::All(value) => Some(value),
+            PerSuper::Each(values) => Self::entry(values, from_type).map(|(_, value)| value),
+        }
+    }
+
+    /// Get the most specific of the given keyed values that applies to the given super type.
+    fn entry<'a>(values: &'a [(Path, T)], from_type: &TypeWithParams) -> Option<&'a (Path, T)> {
+        values
+            .iter()
+            .filter(|(key, _)| from_type.matches_key(key))
+            .max_by_key(|(key, _)| key.segments.len())
+    }
+
+    /// Get all the keys that specific values were given for.
+    pub(crate) fn keys(&self) -> impl Iterator<Item = &Path> {
+        let keys = match self {
+            PerSuper::All(_) => &[][..],
+            PerSuper::Each(values) => &values[..],
+        };
+        keys.iter().map(|(key, _)| key)
+    }
+}
+
+impl<T: FromMeta> FromMeta for PerSuper<T> {
+    fn from_word() -> darling::Result<Self> {
+        T::from_word().map(PerSuper::All)
+    }
+
+    fn from_value(value: &syn::Lit) -> darling::Result<Self> {
+        T::from_value(value).map(PerSuper::All)
+    }
+
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let mut errors = darling::Error::accumulator();
+        let mut values = Vec::new();
+
+        for item in items {
+            match item {
+                NestedMeta::Meta(meta) => {
+                    if let Some(value) = errors.handle(T::from_meta(meta)) {
+                        values.push((meta.path().clone(), value));
+                    }
+                }
+                NestedMeta::Lit(lit) => {
+                    errors.push(
+                        darling::Error::custom("expected `SuperType = value`").with_span(lit),
+                    );
+                }
+            }
+        }
+
+        errors.finish_with(PerSuper::Each(values))
+    }
+}
+
+/// Get the keys that specific values were given for in an optional per-super option.
+pub(crate) fn keys<T>(option: &Option<PerSuper<T>>) -> Vec<&Path> {
+    option.iter().flat_map(PerSuper::keys).collect()
+}
+
+/// Make sure that every key used in a per-super option refers to one of the super types,
+/// and that no super type is matched by several keys of the same specificity.
+pub(crate) fn check_keys(
+    keys: Vec<&Path>,
+    from_types: &[TypeWithParams],
+) -> Result<(), syn::Error> {
+    for (index, key) in keys.iter().enumerate() {
+        let ambiguous = keys[..index].iter().any(|other| {
+            other.segments.len() == key.segments.len()
+                && from_types
+                    .iter()
+                    .any(|from_type| from_type.matches_key(key) && from_type.matches_key(other))
+        });
+        if ambiguous {
+            return Err(syn::Error::new_spanned(
+                key,
+                "This refers to the same super type as a previous key of this option",
+            ));
+        }
+    }
+
+    for key in keys {
+        if !from_types
+            .iter()
+            .any(|from_type| from_type.matches_key(key))
+        {
+            return Err(syn::Error::new_spanned(
+                key,
+                "This does not refer to any of the super types given by from_type",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Make sure that no key of an option whose value needs to be unique for each super type,
+/// e.g. because it names a generated item, applies to several of the super types.
+/// Keys cannot tell apart super types that only differ in references or generic arguments,
+/// such as `Bar` and `&'a Bar`, or `Bar<u8>` and `Bar<u16>`.
+pub(crate) fn check_unique<T>(
+    option: &PerSuper<T>,
+    from_types: &[TypeWithParams],
+    name: &str,
+) -> Result<(), syn::Error> {
+    let values = match option {
+        PerSuper::All(_) => return Ok(()),
+        PerSuper::Each(values) => values,
+    };
+
+    for entry in values.iter() {
+        let mut matching = from_types.iter().filter(|from_type| {
+            PerSuper::entry(values, from_type).is_some_and(|other| std::ptr::eq(entry, other))
+        });
+        if let (Some(first), Some(second)) = (matching.next(), matching.next()) {
+            return Err(syn::Error::new_spanned(
+                &entry.0,
+                format!(
+                    "This refers to several super types, `{}` and `{}`, but {} needs a separate value for each of them. Super types that only differ in references or generic arguments cannot be told apart.",
+                    first.ty.to_token_stream(),
+                    second.ty.to_token_stream(),
+                    name
+                ),
+            ));
+        }
+    }
+
+    Ok(())
+}